@@ -0,0 +1,71 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use growthbook_rust_sdk::client::GrowthBookClient;
+use growthbook_rust_sdk::model_public::GrowthBookAttribute;
+use serde_json::{json, Map, Value};
+use tokio::runtime::Runtime;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const PAYLOAD_SIZES: [usize; 3] = [10, 1_000, 10_000];
+
+fn payload(total_features: usize) -> Value {
+    let mut features = Map::new();
+    for index in 0..total_features {
+        features.insert(
+            format!("feature-{index}"),
+            json!({
+                "defaultValue": false,
+                "rules": [
+                    {
+                        "condition": { "country": { "$in": ["BR", "US"] } },
+                        "force": true
+                    },
+                    {
+                        "key": format!("experiment-{index}"),
+                        "hashAttribute": "id",
+                        "variations": [false, true],
+                        "weights": [0.5, 0.5],
+                        "coverage": 1.0
+                    }
+                ]
+            }),
+        );
+    }
+    json!({ "features": features })
+}
+
+async fn client(total_features: usize) -> (MockServer, GrowthBookClient) {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/features/bench-sdk-key"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(payload(total_features).to_string()))
+        .mount(&mock_server)
+        .await;
+
+    let growthbook = GrowthBookClient::new(&mock_server.uri(), "bench-sdk-key", None, None)
+        .await
+        .expect("Failed to create growthbook client");
+    (mock_server, growthbook)
+}
+
+fn evaluation(criterion: &mut Criterion) {
+    let runtime = Runtime::new().expect("Failed to create runtime");
+    let attributes = GrowthBookAttribute::from(json!({
+        "id": "018fcf11-bb67-7789-8d10-fcbb7de4ff7b",
+        "country": "CA",
+    }))
+    .expect("Failed to create attributes");
+
+    let mut group = criterion.benchmark_group("is_on");
+    for total_features in PAYLOAD_SIZES {
+        let (_mock_server, growthbook) = runtime.block_on(client(total_features));
+        let _guard = runtime.enter();
+        group.bench_with_input(BenchmarkId::from_parameter(total_features), &total_features, |bencher, _| {
+            bencher.iter(|| growthbook.is_on("feature-0", Some(attributes.clone())))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, evaluation);
+criterion_main!(benches);
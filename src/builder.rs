@@ -0,0 +1,359 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use serde_json::Value;
+use url::Url;
+
+use crate::client::{GrowthBookClient, HttpClientOptions};
+use crate::env::Environment;
+use crate::error::{ConfigurationError, GrowthbookError};
+use crate::model_public::{BackoffPolicy, FeaturesCache, FeaturesPayload, GrowthBookAttribute, RefreshStrategy};
+use crate::payload::FeaturesPayloadParser;
+use crate::sticky_bucket::service::StickyBucketService;
+use crate::tracking::{FeatureUsageCallback, FeatureUsageOptions, TrackingCallback};
+
+#[derive(Default)]
+pub struct GrowthBookClientBuilder {
+    api_host: Option<String>,
+    client_key: Option<String>,
+    decryption_key: Option<String>,
+    refresh_strategy: RefreshStrategy,
+    update_interval: Option<Duration>,
+    http_timeout: Option<Duration>,
+    headers: Vec<(String, String)>,
+    bootstrap: Option<FeaturesPayload>,
+    cache_path: Option<PathBuf>,
+    cache_max_staleness: Option<Duration>,
+    non_blocking: bool,
+    forced_features: HashMap<String, Value>,
+    forced_variations: HashMap<String, i64>,
+    tracking_callback: Option<Arc<dyn TrackingCallback>>,
+    feature_usage_callback: Option<(Arc<dyn FeatureUsageCallback>, FeatureUsageOptions)>,
+    sticky_bucket_service: Option<Arc<dyn StickyBucketService>>,
+    attributes: Vec<GrowthBookAttribute>,
+    backoff_policy: Option<BackoffPolicy>,
+    query_string_overrides: bool,
+}
+
+impl GrowthBookClientBuilder {
+    pub fn from_env() -> Result<Self, GrowthbookError> {
+        let mut builder = GrowthBookClientBuilder::default()
+            .api_host(&Environment::required_string("GB_URL")?)
+            .client_key(&Environment::required_string("GB_SDK_KEY")?)
+            .query_string_overrides(Environment::bool_or_default("GB_QA_MODE", false)?);
+        if let Some(decryption_key) = Environment::optional_string("GB_DECRYPTION_KEY") {
+            builder = builder.decryption_key(&decryption_key);
+        }
+        if let Some(seconds) = Environment::optional_u64("GB_UPDATE_INTERVAL")? {
+            builder = builder.update_interval(Duration::from_secs(seconds));
+        }
+        if let Some(seconds) = Environment::optional_u64("GB_HTTP_CLIENT_TIMEOUT")? {
+            builder = builder.http_timeout(Duration::from_secs(seconds));
+        }
+        if Environment::bool_or_default("GB_STREAMING", false)? {
+            builder = builder.refresh_strategy(RefreshStrategy::Streaming);
+        }
+        if let Some(cache_path) = Environment::optional_string("GB_CACHE_PATH") {
+            builder = builder.cache_path(cache_path);
+        }
+        Ok(builder)
+    }
+
+    pub fn api_host(
+        self,
+        api_host: &str,
+    ) -> Self {
+        Self {
+            api_host: Some(String::from(api_host)),
+            ..self
+        }
+    }
+
+    pub fn client_key(
+        self,
+        client_key: &str,
+    ) -> Self {
+        Self {
+            client_key: Some(String::from(client_key)),
+            ..self
+        }
+    }
+
+    pub fn decryption_key(
+        self,
+        decryption_key: &str,
+    ) -> Self {
+        Self {
+            decryption_key: Some(String::from(decryption_key)),
+            ..self
+        }
+    }
+
+    pub fn refresh_strategy(
+        self,
+        refresh_strategy: RefreshStrategy,
+    ) -> Self {
+        Self { refresh_strategy, ..self }
+    }
+
+    pub fn update_interval(
+        self,
+        update_interval: Duration,
+    ) -> Self {
+        Self {
+            update_interval: Some(update_interval),
+            ..self
+        }
+    }
+
+    pub fn http_timeout(
+        self,
+        http_timeout: Duration,
+    ) -> Self {
+        Self {
+            http_timeout: Some(http_timeout),
+            ..self
+        }
+    }
+
+    pub fn header(
+        mut self,
+        name: &str,
+        value: &str,
+    ) -> Self {
+        self.headers.push((String::from(name), String::from(value)));
+        self
+    }
+
+    pub fn bootstrap(
+        self,
+        payload: impl Into<FeaturesPayload>,
+    ) -> Self {
+        Self {
+            bootstrap: Some(payload.into()),
+            ..self
+        }
+    }
+
+    pub fn cache_path(
+        self,
+        cache_path: impl Into<PathBuf>,
+    ) -> Self {
+        Self {
+            cache_path: Some(cache_path.into()),
+            ..self
+        }
+    }
+
+    pub fn cache_max_staleness(
+        self,
+        max_staleness: Duration,
+    ) -> Self {
+        Self {
+            cache_max_staleness: Some(max_staleness),
+            ..self
+        }
+    }
+
+    pub fn non_blocking(
+        self,
+        non_blocking: bool,
+    ) -> Self {
+        Self { non_blocking, ..self }
+    }
+
+    pub fn forced_feature(
+        mut self,
+        feature_name: &str,
+        value: Value,
+    ) -> Self {
+        self.forced_features.insert(String::from(feature_name), value);
+        self
+    }
+
+    pub fn forced_variation(
+        mut self,
+        experiment_key: &str,
+        variation: i64,
+    ) -> Self {
+        self.forced_variations.insert(String::from(experiment_key), variation);
+        self
+    }
+
+    pub fn tracking_callback(
+        self,
+        tracking_callback: Arc<dyn TrackingCallback>,
+    ) -> Self {
+        Self {
+            tracking_callback: Some(tracking_callback),
+            ..self
+        }
+    }
+
+    pub fn feature_usage_callback(
+        self,
+        feature_usage_callback: Arc<dyn FeatureUsageCallback>,
+        options: FeatureUsageOptions,
+    ) -> Self {
+        Self {
+            feature_usage_callback: Some((feature_usage_callback, options)),
+            ..self
+        }
+    }
+
+    pub fn sticky_bucket_service(
+        self,
+        sticky_bucket_service: Arc<dyn StickyBucketService>,
+    ) -> Self {
+        Self {
+            sticky_bucket_service: Some(sticky_bucket_service),
+            ..self
+        }
+    }
+
+    pub fn attributes(
+        self,
+        attributes: Vec<GrowthBookAttribute>,
+    ) -> Self {
+        Self { attributes, ..self }
+    }
+
+    pub fn backoff_policy(
+        self,
+        backoff_policy: BackoffPolicy,
+    ) -> Self {
+        Self {
+            backoff_policy: Some(backoff_policy),
+            ..self
+        }
+    }
+
+    pub fn query_string_overrides(
+        self,
+        enabled: bool,
+    ) -> Self {
+        Self {
+            query_string_overrides: enabled,
+            ..self
+        }
+    }
+
+    pub async fn build(mut self) -> Result<GrowthBookClient, GrowthbookError> {
+        let options = self.options()?;
+        let client = if self.non_blocking {
+            GrowthBookClient::create_non_blocking(options)?
+        } else {
+            GrowthBookClient::create(options).await?
+        };
+
+        let mut client = client
+            .with_forced_features(self.forced_features)
+            .with_forced_variations(self.forced_variations)
+            .with_attributes(self.attributes)
+            .with_query_string_overrides(self.query_string_overrides);
+        if let Some(backoff_policy) = self.backoff_policy {
+            client = client.with_backoff_policy(backoff_policy);
+        }
+        if let Some(tracking_callback) = self.tracking_callback {
+            client = client.with_tracking_callback(tracking_callback);
+        }
+        if let Some((feature_usage_callback, options)) = self.feature_usage_callback {
+            client = client.with_feature_usage_callback(feature_usage_callback, options);
+        }
+        if let Some(sticky_bucket_service) = self.sticky_bucket_service {
+            client = client.with_sticky_bucket_service(sticky_bucket_service);
+        }
+        Ok(client)
+    }
+
+    fn options(&mut self) -> Result<HttpClientOptions, GrowthbookError> {
+        let api_url = validate_api_host(self.api_host.as_deref())?;
+        let sdk_key = self.client_key.as_deref().map(str::trim).filter(|it| !it.is_empty()).ok_or(ConfigurationError::MissingClientKey)?;
+        if let Some(decryption_key) = &self.decryption_key {
+            validate_decryption_key(decryption_key)?;
+        }
+        if self.update_interval.is_some_and(|it| it.is_zero()) {
+            return Err(ConfigurationError::InvalidUpdateInterval.into());
+        }
+        if self.http_timeout.is_some_and(|it| it.is_zero()) {
+            return Err(ConfigurationError::InvalidHttpTimeout.into());
+        }
+        if let Some((experiment_key, _)) = self.forced_variations.iter().find(|(_, variation)| **variation < 0) {
+            return Err(ConfigurationError::InvalidForcedVariation(experiment_key.clone()).into());
+        }
+
+        let bootstrap = self
+            .bootstrap
+            .take()
+            .map(|payload| FeaturesPayloadParser::parse_payload(payload, self.decryption_key.as_deref()))
+            .transpose()
+            .map_err(|e| ConfigurationError::InvalidBootstrapPayload(e.message))?;
+
+        Ok(HttpClientOptions {
+            api_url,
+            sdk_key: String::from(sdk_key),
+            decryption_key: self.decryption_key.clone(),
+            refresh_strategy: self.refresh_strategy,
+            update_interval: self.update_interval,
+            http_timeout: self.http_timeout,
+            headers: headers(&self.headers)?,
+            bootstrap,
+            cache: self.cache()?,
+        })
+    }
+
+    fn cache(&self) -> Result<Option<FeaturesCache>, ConfigurationError> {
+        let Some(path) = &self.cache_path else {
+            return Ok(None);
+        };
+
+        let directory_exists = path.parent().is_none_or(|parent| parent.as_os_str().is_empty() || parent.is_dir());
+        if !directory_exists || path.is_dir() {
+            return Err(ConfigurationError::InvalidCachePath(path.clone()));
+        }
+        Ok(Some(FeaturesCache {
+            path: path.clone(),
+            max_staleness: self.cache_max_staleness,
+        }))
+    }
+}
+
+impl GrowthBookClient {
+    pub fn builder() -> GrowthBookClientBuilder {
+        GrowthBookClientBuilder::default()
+    }
+
+    pub async fn from_env() -> Result<Self, GrowthbookError> {
+        GrowthBookClientBuilder::from_env()?.build().await
+    }
+}
+
+fn validate_api_host(api_host: Option<&str>) -> Result<String, ConfigurationError> {
+    let api_host = api_host.map(str::trim).filter(|it| !it.is_empty()).ok_or(ConfigurationError::MissingApiHost)?;
+    match Url::parse(api_host) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") && url.has_host() => Ok(String::from(api_host.trim_end_matches('/'))),
+        _ => Err(ConfigurationError::InvalidApiHost(String::from(api_host))),
+    }
+}
+
+fn validate_decryption_key(decryption_key: &str) -> Result<(), ConfigurationError> {
+    match STANDARD.decode(decryption_key) {
+        Ok(key) if key.len() == 16 || key.len() == 32 => Ok(()),
+        _ => Err(ConfigurationError::InvalidDecryptionKey),
+    }
+}
+
+fn headers(headers: &[(String, String)]) -> Result<HeaderMap, ConfigurationError> {
+    let mut header_map = HeaderMap::new();
+    for (name, value) in headers {
+        let header_name = HeaderName::from_bytes(name.as_bytes()).map_err(|_| ConfigurationError::InvalidHeader(name.clone()))?;
+        let header_value = HeaderValue::from_str(value).map_err(|_| ConfigurationError::InvalidHeader(name.clone()))?;
+        header_map.append(header_name, header_value);
+    }
+    Ok(header_map)
+}
@@ -0,0 +1,81 @@
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+use tokio::io::AsyncWriteExt;
+
+use crate::error::{GrowthbookError, GrowthbookErrorCode};
+use crate::model_public::FeaturesCache;
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CachedFeatures {
+    pub fetched_at: DateTime<Utc>,
+    pub etag: Option<String>,
+    pub payload: Box<RawValue>,
+}
+
+pub struct FeaturesCacheFile;
+
+impl FeaturesCacheFile {
+    pub async fn write(
+        cache: &FeaturesCache,
+        payload: &str,
+        etag: Option<String>,
+    ) -> Result<(), GrowthbookError> {
+        let cached_features = CachedFeatures {
+            fetched_at: Utc::now(),
+            etag,
+            payload: RawValue::from_string(String::from(payload))?,
+        };
+        Self::persist(cache, &cached_features).await
+    }
+
+    // a 304 confirms the cached payload is still current, as long as it is the version the etag points to
+    pub async fn touch(
+        cache: &FeaturesCache,
+        etag: &str,
+    ) -> Result<(), GrowthbookError> {
+        let content = tokio::fs::read(&cache.path).await?;
+        let mut cached_features = serde_json::from_slice::<CachedFeatures>(&content)?;
+        if cached_features.etag.as_deref() != Some(etag) {
+            return Ok(());
+        }
+
+        cached_features.fetched_at = Utc::now();
+        Self::persist(cache, &cached_features).await
+    }
+
+    async fn persist(
+        cache: &FeaturesCache,
+        cached_features: &CachedFeatures,
+    ) -> Result<(), GrowthbookError> {
+        let content = serde_json::to_vec(cached_features)?;
+
+        let mut temporary_path = cache.path.clone().into_os_string();
+        temporary_path.push(".tmp");
+        let temporary_path = PathBuf::from(temporary_path);
+        let mut file = tokio::fs::File::create(&temporary_path).await?;
+        file.write_all(&content).await?;
+        file.sync_all().await?;
+        tokio::fs::rename(&temporary_path, &cache.path).await?;
+        Ok(())
+    }
+
+    pub fn read(cache: &FeaturesCache) -> Result<CachedFeatures, GrowthbookError> {
+        let content = std::fs::read(&cache.path)?;
+        let cached_features = serde_json::from_slice::<CachedFeatures>(&content)?;
+
+        if let Some(max_staleness) = cache.max_staleness {
+            let age = Utc::now().signed_duration_since(cached_features.fetched_at).to_std().unwrap_or_default();
+            if age > max_staleness {
+                return Err(GrowthbookError::new(
+                    GrowthbookErrorCode::FeaturesCacheError,
+                    &format!("Cached features fetched at {} are older than {max_staleness:?}", cached_features.fetched_at),
+                ));
+            }
+        }
+        Ok(cached_features)
+    }
+}
@@ -1,45 +1,155 @@
 use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
+use std::path::Path;
+use std::sync::Arc;
 use std::time::Duration;
 
+use arc_swap::{ArcSwap, Guard};
+use chrono::{DateTime, Utc};
+use reqwest::header::HeaderMap;
+use serde_json::Value;
 use tokio::time::sleep;
 use tracing::error;
 
+use crate::condition::saved_group::SavedGroups;
+use crate::dto::{GrowthBookFeature, GrowthBookResponse};
 use crate::env::Environment;
-use crate::error::GrowthbookError;
+use crate::error::{GrowthbookError, GrowthbookErrorCode};
 use crate::gateway::GrowthbookGateway;
 use crate::growthbook::GrowthBook;
 use crate::model_private::FeatureResult;
-use crate::model_public::GrowthBookAttribute;
+use crate::model_public::{BackoffPolicy, Experiment, ExperimentResult, FeaturesCache, FeaturesPayload, GrowthBookAttribute, NonBlockingOptions, RefreshStrategy, UrlRedirect};
+use crate::payload::FeaturesPayloadParser;
+use crate::refresh::{RefreshState, RefreshTask};
+use crate::source::{FeatureSource, FileSource, FileVersion, HttpPollingSource, ServerSentEventsSource};
+use crate::sticky_bucket::service::StickyBucketService;
+use crate::tracking::{ExperimentTracker, FeatureUsageCallback, FeatureUsageOptions, FeatureUsageTracker, TrackingCallback};
+
+#[derive(Default)]
+pub(crate) struct HttpClientOptions {
+    pub api_url: String,
+    pub sdk_key: String,
+    pub decryption_key: Option<String>,
+    pub refresh_strategy: RefreshStrategy,
+    pub update_interval: Option<Duration>,
+    pub http_timeout: Option<Duration>,
+    pub headers: HeaderMap,
+    pub bootstrap: Option<GrowthBookResponse>,
+    pub cache: Option<FeaturesCache>,
+}
+
+impl HttpClientOptions {
+    fn new(
+        api_url: &str,
+        sdk_key: &str,
+        decryption_key: Option<&str>,
+        update_interval: Option<Duration>,
+        http_timeout: Option<Duration>,
+    ) -> Self {
+        HttpClientOptions {
+            api_url: String::from(api_url),
+            sdk_key: String::from(sdk_key),
+            decryption_key: decryption_key.map(String::from),
+            update_interval,
+            http_timeout,
+            ..HttpClientOptions::default()
+        }
+    }
+}
 
 #[derive(Clone)]
 pub struct GrowthBookClient {
-    pub gb: Arc<RwLock<GrowthBook>>,
+    pub(crate) gb: Arc<ArcSwap<GrowthBook>>,
+    refresh_state: Arc<RefreshState>,
+    refresh_task: Arc<RefreshTask>,
 }
 
-async fn updated_features_task(
-    growthbook_gateway: GrowthbookGateway,
-    config: Arc<RwLock<GrowthBook>>,
+enum InitialSnapshot {
+    Fetched,
+    Cached,
+    Bootstrap,
+}
+
+enum RetryDecision {
+    RetrySoon,
+    BackOff(Option<Duration>),
+    Stop,
+}
+
+impl From<&GrowthbookError> for RetryDecision {
+    fn from(error: &GrowthbookError) -> Self {
+        match error.code {
+            GrowthbookErrorCode::GrowthbookGatewayInvalidSdkKey | GrowthbookErrorCode::GrowthbookGatewayNotFound => RetryDecision::Stop,
+            GrowthbookErrorCode::GrowthbookGatewayRateLimited { retry_after } => RetryDecision::BackOff(retry_after),
+            GrowthbookErrorCode::GrowthbookGatewayTimeout | GrowthbookErrorCode::GrowthbookGateway => RetryDecision::RetrySoon,
+            _ => RetryDecision::BackOff(None),
+        }
+    }
+}
+
+async fn refresh_features_task(
+    mut source: Box<dyn FeatureSource>,
+    config: Arc<ArcSwap<GrowthBook>>,
+    refresh_state: Arc<RefreshState>,
     interval: Duration,
 ) {
     loop {
-        match growthbook_gateway.get_features(None).await {
-            Ok(new_config) => {
-                let mut writable_config = config.write().expect("problem to create mutex for gb data");
-                let updated_features = GrowthBook {
-                    forced_variations: new_config.forced_variations,
-                    features: new_config.features,
-                };
-                *writable_config = updated_features;
+        match source.next().await {
+            Ok(Some(new_config)) => {
+                apply_features(&config, new_config);
+                refresh_state.record_success();
             },
+            Ok(None) => refresh_state.record_success(),
             Err(e) => {
-                error!("[growthbook-sdk] Failed to fetch features from server: {:?}", e);
+                let failures = refresh_state.record_failure();
+                error!("[growthbook-sdk] Failed to refresh features ({} consecutive failures): {:?}", failures, e);
+                let delay = match RetryDecision::from(&e) {
+                    // an outage must never make the client poll faster than it does when healthy
+                    RetryDecision::RetrySoon => refresh_state.failure_delay(failures, interval).max(interval),
+                    RetryDecision::BackOff(retry_after) => back_off_delay(&refresh_state, retry_after, interval),
+                    RetryDecision::Stop => {
+                        error!("[growthbook-sdk] Stopping features refresh, the sdk key was rejected: {}", e);
+                        refresh_state.record_stop();
+                        return;
+                    },
+                };
+                sleep(delay).await;
             },
         }
-        sleep(interval).await;
     }
 }
 
+fn back_off_delay(
+    refresh_state: &RefreshState,
+    retry_after: Option<Duration>,
+    interval: Duration,
+) -> Duration {
+    let failure_delay = refresh_state.failure_delay(refresh_state.failures(), interval);
+    retry_after.unwrap_or(interval).max(failure_delay)
+}
+
+fn update_interval(update_interval: Option<Duration>) -> Duration {
+    update_interval.unwrap_or_else(|| {
+        let seconds = Environment::u64_or_default("GB_UPDATE_INTERVAL", 60);
+        Duration::from_secs(seconds)
+    })
+}
+
+fn apply_features(
+    config: &ArcSwap<GrowthBook>,
+    new_config: GrowthBookResponse,
+) {
+    let features = Arc::new(new_config.features);
+    let saved_groups = Arc::new(SavedGroups::new(new_config.saved_groups));
+    let experiments = Arc::new(new_config.experiments);
+    config.rcu(|current| GrowthBook {
+        forced_variations: new_config.forced_variations.clone(),
+        features: Arc::clone(&features),
+        saved_groups: Arc::clone(&saved_groups),
+        experiments: Arc::clone(&experiments),
+        ..GrowthBook::clone(current)
+    });
+}
+
 impl GrowthBookClient {
     pub async fn new(
         api_url: &str,
@@ -47,27 +157,296 @@ impl GrowthBookClient {
         update_interval: Option<Duration>,
         http_timeout: Option<Duration>,
     ) -> Result<Self, GrowthbookError> {
-        let default_interval = update_interval.unwrap_or_else(|| {
-            let seconds = Environment::u64_or_default("GB_UPDATE_INTERVAL", 60);
-            Duration::from_secs(seconds)
-        });
-        let default_timeout = http_timeout.unwrap_or_else(|| {
+        Self::create(HttpClientOptions::new(api_url, sdk_key, None, update_interval, http_timeout)).await
+    }
+
+    pub async fn new_with_decryption_key(
+        api_url: &str,
+        sdk_key: &str,
+        decryption_key: &str,
+        update_interval: Option<Duration>,
+        http_timeout: Option<Duration>,
+    ) -> Result<Self, GrowthbookError> {
+        Self::create(HttpClientOptions::new(api_url, sdk_key, Some(decryption_key), update_interval, http_timeout)).await
+    }
+
+    pub async fn new_with_refresh_strategy(
+        api_url: &str,
+        sdk_key: &str,
+        decryption_key: Option<&str>,
+        refresh_strategy: RefreshStrategy,
+        update_interval: Option<Duration>,
+        http_timeout: Option<Duration>,
+    ) -> Result<Self, GrowthbookError> {
+        Self::create(HttpClientOptions {
+            refresh_strategy,
+            ..HttpClientOptions::new(api_url, sdk_key, decryption_key, update_interval, http_timeout)
+        })
+        .await
+    }
+
+    pub async fn new_with_cache(
+        api_url: &str,
+        sdk_key: &str,
+        decryption_key: Option<&str>,
+        cache: FeaturesCache,
+        update_interval: Option<Duration>,
+        http_timeout: Option<Duration>,
+    ) -> Result<Self, GrowthbookError> {
+        Self::create(HttpClientOptions {
+            cache: Some(cache),
+            ..HttpClientOptions::new(api_url, sdk_key, decryption_key, update_interval, http_timeout)
+        })
+        .await
+    }
+
+    pub fn new_non_blocking(
+        api_url: &str,
+        sdk_key: &str,
+        options: NonBlockingOptions,
+    ) -> Result<Self, GrowthbookError> {
+        let bootstrap = options
+            .bootstrap
+            .map(|payload| FeaturesPayloadParser::parse_payload(payload, options.decryption_key.as_deref()))
+            .transpose()?;
+        Self::create_non_blocking(HttpClientOptions {
+            refresh_strategy: options.refresh_strategy,
+            bootstrap,
+            cache: options.cache,
+            ..HttpClientOptions::new(api_url, sdk_key, options.decryption_key.as_deref(), options.update_interval, options.http_timeout)
+        })
+    }
+
+    pub(crate) async fn create(options: HttpClientOptions) -> Result<Self, GrowthbookError> {
+        let gb_gateway = Self::gateway(&options)?;
+        let refresh_state = Arc::new(RefreshState::default());
+        let mut source = Self::http_source(gb_gateway.clone(), &options, &refresh_state);
+        match source.next().await {
+            Ok(resp) => Self::start(source, resp.unwrap_or_default(), InitialSnapshot::Fetched, options.update_interval, refresh_state),
+            Err(e) => {
+                let (resp, initial_snapshot) = match (gb_gateway.load_cached_features(), options.bootstrap) {
+                    (Some(resp), _) => (resp, InitialSnapshot::Cached),
+                    (None, Some(resp)) => (resp, InitialSnapshot::Bootstrap),
+                    (None, None) => return Err(e),
+                };
+                error!("[growthbook-sdk] Failed to fetch features from server, serving fallback features: {:?}", e);
+                Self::start(source, resp, initial_snapshot, options.update_interval, refresh_state)
+            },
+        }
+    }
+
+    pub(crate) fn create_non_blocking(options: HttpClientOptions) -> Result<Self, GrowthbookError> {
+        let gb_gateway = Self::gateway(&options)?;
+        let refresh_state = Arc::new(RefreshState::default());
+        let source = Self::http_source(gb_gateway.clone(), &options, &refresh_state);
+        let (resp, initial_snapshot) = match (gb_gateway.load_cached_features(), options.bootstrap) {
+            (Some(resp), _) => (resp, InitialSnapshot::Cached),
+            (None, Some(resp)) => (resp, InitialSnapshot::Bootstrap),
+            (None, None) => (GrowthBookResponse::default(), InitialSnapshot::Bootstrap),
+        };
+        Self::start(source, resp, initial_snapshot, options.update_interval, refresh_state)
+    }
+
+    pub async fn from_source(
+        source: impl FeatureSource + 'static,
+        update_interval: Option<Duration>,
+    ) -> Result<Self, GrowthbookError> {
+        let mut source: Box<dyn FeatureSource> = Box::new(source);
+        let resp = source.next().await?;
+        Self::start(source, resp.unwrap_or_default(), InitialSnapshot::Fetched, update_interval, Arc::new(RefreshState::default()))
+    }
+
+    fn gateway(options: &HttpClientOptions) -> Result<GrowthbookGateway, GrowthbookError> {
+        let default_timeout = options.http_timeout.unwrap_or_else(|| {
             let seconds = Environment::u64_or_default("GB_HTTP_CLIENT_TIMEOUT", 10);
             Duration::from_secs(seconds)
         });
-        let gb_gateway = GrowthbookGateway::new(api_url, sdk_key, default_timeout)?;
-        let resp = gb_gateway.get_features(None).await?;
-        let growthbook_writable = Arc::new(RwLock::new(GrowthBook {
-            forced_variations: resp.forced_variations,
-            features: resp.features,
-        }));
+        let gateway = GrowthbookGateway::new(&options.api_url, &options.sdk_key, options.decryption_key.as_deref(), default_timeout)?;
+        Ok(gateway.with_cache(options.cache.clone()).with_headers(options.headers.clone()))
+    }
+
+    fn http_source(
+        gb_gateway: GrowthbookGateway,
+        options: &HttpClientOptions,
+        refresh_state: &RefreshState,
+    ) -> Box<dyn FeatureSource> {
+        let default_interval = update_interval(options.update_interval);
+        match options.refresh_strategy {
+            RefreshStrategy::Polling => Box::new(HttpPollingSource::from_gateway(gb_gateway, default_interval).with_shared_backoff_policy(refresh_state.shared_backoff_policy())),
+            RefreshStrategy::Streaming => Box::new(ServerSentEventsSource::from_gateway(gb_gateway, default_interval).with_shared_backoff_policy(refresh_state.shared_backoff_policy())),
+        }
+    }
+
+    fn start(
+        source: Box<dyn FeatureSource>,
+        resp: GrowthBookResponse,
+        initial_snapshot: InitialSnapshot,
+        update_interval: Option<Duration>,
+        refresh_state: Arc<RefreshState>,
+    ) -> Result<Self, GrowthbookError> {
+        let growthbook_writable = Self::writable_snapshot(resp);
         let gb_rw_clone = Arc::clone(&growthbook_writable);
+        match initial_snapshot {
+            InitialSnapshot::Fetched => refresh_state.record_success(),
+            InitialSnapshot::Cached => refresh_state.record_cache_load(),
+            InitialSnapshot::Bootstrap => {},
+        }
+        let refresh_state_clone = Arc::clone(&refresh_state);
 
-        tokio::spawn(async move {
-            updated_features_task(gb_gateway, gb_rw_clone, default_interval).await;
-        });
+        let refresh_task = RefreshTask::spawn(
+            Arc::clone(&refresh_state),
+            refresh_features_task(source, gb_rw_clone, refresh_state_clone, self::update_interval(update_interval)),
+        )?;
+
+        Ok(GrowthBookClient {
+            gb: growthbook_writable,
+            refresh_state,
+            refresh_task: Arc::new(refresh_task),
+        })
+    }
+
+    pub fn from_payload(payload: impl Into<FeaturesPayload>) -> Result<Self, GrowthbookError> {
+        let resp = FeaturesPayloadParser::parse_payload(payload.into(), None)?;
+        let refresh_state = Arc::new(RefreshState::default());
+        refresh_state.record_success();
+
+        Ok(GrowthBookClient {
+            gb: Self::writable_snapshot(resp),
+            refresh_state,
+            refresh_task: Arc::new(RefreshTask::default()),
+        })
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, GrowthbookError> {
+        let payload = std::fs::read_to_string(path)?;
+        Self::from_payload(payload)
+    }
+
+    pub fn from_file_watched(
+        path: impl AsRef<Path>,
+        watch_interval: Option<Duration>,
+    ) -> Result<Self, GrowthbookError> {
+        let path = path.as_ref().to_path_buf();
+        let version = FileVersion::read(&path)?;
+        let client = Self::from_file(&path)?;
+        let default_interval = watch_interval.unwrap_or(Duration::from_secs(1));
+
+        let source = Box::new(FileSource::loaded(path, default_interval, version));
+        let refresh_task = RefreshTask::spawn(
+            Arc::clone(&client.refresh_state),
+            refresh_features_task(source, Arc::clone(&client.gb), Arc::clone(&client.refresh_state), default_interval),
+        )?;
+        Ok(GrowthBookClient {
+            refresh_task: Arc::new(refresh_task),
+            ..client
+        })
+    }
+
+    fn writable_snapshot(resp: GrowthBookResponse) -> Arc<ArcSwap<GrowthBook>> {
+        Arc::new(ArcSwap::from_pointee(GrowthBook {
+            forced_variations: resp.forced_variations,
+            features: Arc::new(resp.features),
+            saved_groups: Arc::new(SavedGroups::new(resp.saved_groups)),
+            experiments: Arc::new(resp.experiments),
+            query_string_overrides: false,
+            sticky_bucket_service: None,
+            experiment_tracker: None,
+            feature_usage_tracker: None,
+            forced_features: Arc::new(HashMap::new()),
+            forced_variation_overrides: Arc::new(HashMap::new()),
+            attributes: Arc::new(vec![]),
+        }))
+    }
+
+    pub async fn shutdown(&self) {
+        self.refresh_task.shutdown().await;
+    }
+
+    pub async fn ready(
+        &self,
+        timeout: Option<Duration>,
+    ) -> Result<(), GrowthbookError> {
+        self.refresh_state.ready(timeout).await
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.refresh_state.is_ready()
+    }
+
+    pub fn is_serving_cached_features(&self) -> bool {
+        self.refresh_state.is_serving_cache()
+    }
+
+    pub fn is_refreshing(&self) -> bool {
+        self.refresh_task.is_running()
+    }
+
+    pub fn with_backoff_policy(
+        self,
+        backoff_policy: BackoffPolicy,
+    ) -> Self {
+        self.refresh_state.set_backoff_policy(backoff_policy);
+        self
+    }
+
+    pub fn with_forced_features(
+        self,
+        forced_features: HashMap<String, Value>,
+    ) -> Self {
+        let forced_features = Arc::new(forced_features);
+        self.update(|gb| gb.forced_features = Arc::clone(&forced_features));
+        self
+    }
+
+    pub fn with_forced_variations(
+        self,
+        forced_variations: HashMap<String, i64>,
+    ) -> Self {
+        let forced_variations = Arc::new(forced_variations);
+        self.update(|gb| gb.forced_variation_overrides = Arc::clone(&forced_variations));
+        self
+    }
+
+    pub fn with_attributes(
+        self,
+        attributes: Vec<GrowthBookAttribute>,
+    ) -> Self {
+        let attributes = Arc::new(attributes);
+        self.update(|gb| gb.attributes = Arc::clone(&attributes));
+        self
+    }
+
+    pub fn with_sticky_bucket_service(
+        self,
+        sticky_bucket_service: Arc<dyn StickyBucketService>,
+    ) -> Self {
+        self.update(|gb| gb.sticky_bucket_service = Some(sticky_bucket_service.clone()));
+        self
+    }
+
+    pub fn with_query_string_overrides(
+        self,
+        enabled: bool,
+    ) -> Self {
+        self.update(|gb| gb.query_string_overrides = enabled);
+        self
+    }
+
+    pub fn with_tracking_callback(
+        self,
+        tracking_callback: Arc<dyn TrackingCallback>,
+    ) -> Self {
+        self.update(|gb| gb.experiment_tracker = Some(Arc::new(ExperimentTracker::new(tracking_callback.clone()))));
+        self
+    }
 
-        Ok(GrowthBookClient { gb: growthbook_writable })
+    pub fn with_feature_usage_callback(
+        self,
+        feature_usage_callback: Arc<dyn FeatureUsageCallback>,
+        options: FeatureUsageOptions,
+    ) -> Self {
+        self.update(|gb| gb.feature_usage_tracker = Some(Arc::new(FeatureUsageTracker::new(feature_usage_callback.clone(), options.clone()))));
+        self
     }
 
     pub fn is_on(
@@ -75,7 +454,7 @@ impl GrowthBookClient {
         feature_name: &str,
         user_attributes: Option<Vec<GrowthBookAttribute>>,
     ) -> bool {
-        self.read_gb().check(feature_name, &user_attributes).on
+        self.read_gb().check(feature_name, &user_attributes, None).on
     }
 
     pub fn is_off(
@@ -83,7 +462,7 @@ impl GrowthBookClient {
         feature_name: &str,
         user_attributes: Option<Vec<GrowthBookAttribute>>,
     ) -> bool {
-        self.read_gb().check(feature_name, &user_attributes).off
+        self.read_gb().check(feature_name, &user_attributes, None).off
     }
 
     pub fn feature_result(
@@ -91,7 +470,41 @@ impl GrowthBookClient {
         feature_name: &str,
         user_attributes: Option<Vec<GrowthBookAttribute>>,
     ) -> FeatureResult {
-        self.read_gb().check(feature_name, &user_attributes)
+        self.read_gb().check(feature_name, &user_attributes, None)
+    }
+
+    pub fn feature_result_for_url(
+        &self,
+        feature_name: &str,
+        user_attributes: Option<Vec<GrowthBookAttribute>>,
+        url: &str,
+    ) -> FeatureResult {
+        self.read_gb().check(feature_name, &user_attributes, Some(url))
+    }
+
+    pub fn run(
+        &self,
+        experiment: &Experiment,
+        user_attributes: Option<Vec<GrowthBookAttribute>>,
+    ) -> ExperimentResult {
+        self.read_gb().run(experiment, &user_attributes, None)
+    }
+
+    pub fn run_for_url(
+        &self,
+        experiment: &Experiment,
+        user_attributes: Option<Vec<GrowthBookAttribute>>,
+        url: &str,
+    ) -> ExperimentResult {
+        self.read_gb().run(experiment, &user_attributes, Some(url))
+    }
+
+    pub fn redirect(
+        &self,
+        url: &str,
+        user_attributes: Option<Vec<GrowthBookAttribute>>,
+    ) -> Option<UrlRedirect> {
+        self.read_gb().redirect(url, &user_attributes)
     }
 
     pub fn total_features(&self) -> usize {
@@ -99,16 +512,46 @@ impl GrowthBookClient {
         gb_data.features.len()
     }
 
-    fn read_gb(&self) -> GrowthBook {
-        match self.gb.read() {
-            Ok(rw_read_guard) => (*rw_read_guard).clone(),
-            Err(e) => {
-                error!("{}", format!("[growthbook-sdk] problem to reading gb mutex data returning empty {:?}", e));
-                GrowthBook {
-                    forced_variations: None,
-                    features: HashMap::new(),
-                }
-            },
-        }
+    // replaces the former public `gb` field, later refreshes never change the returned snapshot
+    pub fn snapshot(&self) -> Arc<GrowthBook> {
+        self.gb.load_full()
+    }
+
+    pub fn features(&self) -> Arc<HashMap<String, GrowthBookFeature>> {
+        Arc::clone(&self.read_gb().features)
+    }
+
+    pub fn feature(
+        &self,
+        feature_name: &str,
+    ) -> Option<GrowthBookFeature> {
+        self.read_gb().features.get(feature_name).cloned()
+    }
+
+    pub fn experiments(&self) -> Arc<Vec<Experiment>> {
+        Arc::clone(&self.read_gb().experiments)
+    }
+
+    pub fn consecutive_failures(&self) -> u32 {
+        self.refresh_state.failures()
+    }
+
+    pub fn last_successful_refresh(&self) -> Option<DateTime<Utc>> {
+        self.refresh_state.last_success()
+    }
+
+    fn read_gb(&self) -> Guard<Arc<GrowthBook>> {
+        self.gb.load()
+    }
+
+    fn update(
+        &self,
+        change: impl Fn(&mut GrowthBook),
+    ) {
+        self.gb.rcu(|current| {
+            let mut updated = GrowthBook::clone(current);
+            change(&mut updated);
+            updated
+        });
     }
 }
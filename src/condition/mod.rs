@@ -1,7 +1,8 @@
-mod elem_match_comparison;
+pub mod model;
 mod operator_condition;
 mod order_comparison;
 mod regex_comparison;
+pub mod saved_group;
 mod size_comparison;
 mod type_comparison;
 pub mod use_case;
@@ -0,0 +1,117 @@
+use std::sync::Arc;
+
+use regex::Regex;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+
+use crate::model_public::GrowthBookAttributeValue;
+
+#[derive(Clone, Debug)]
+pub struct Condition {
+    pub(crate) source: Arc<Value>,
+    pub(crate) nodes: Arc<Vec<ConditionNode>>,
+}
+
+#[derive(Clone, Debug)]
+pub enum ConditionNode {
+    Constant(bool),
+    Not(Vec<ConditionNode>),
+    And(Vec<Vec<ConditionNode>>),
+    Nor(Vec<Vec<ConditionNode>>),
+    Or(Vec<Vec<ConditionNode>>),
+    All(Vec<ConditionNode>),
+    Any(Vec<ConditionNode>),
+    Attribute { path: String, comparison: Comparison },
+}
+
+#[derive(Clone, Debug)]
+pub enum Comparison {
+    Eq(GrowthBookAttributeValue),
+    Ne(GrowthBookAttributeValue),
+    Exists(bool),
+    In {
+        values: Vec<GrowthBookAttributeValue>,
+        case_insensitive: bool,
+    },
+    Nin {
+        values: Vec<GrowthBookAttributeValue>,
+        case_insensitive: bool,
+    },
+    All {
+        values: Vec<GrowthBookAttributeValue>,
+        case_insensitive: bool,
+    },
+    Order {
+        operator: ComparisonOperator,
+        operand: OrderOperand,
+        target: OrderTarget,
+    },
+    Version {
+        operator: ComparisonOperator,
+        version: String,
+    },
+    Regex(Regex),
+    Type(String),
+    Size(i64),
+    InGroup(String),
+    NotInGroup(String),
+    ArrayEquals(Vec<GrowthBookAttributeValue>),
+    Null,
+    Equals(GrowthBookAttributeValue),
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum ComparisonOperator {
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    Eq,
+    Ne,
+}
+
+// what an ordering comparison reads from the user value
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OrderTarget {
+    Value,
+    Size,
+    Elements,
+}
+
+#[derive(Clone, Debug)]
+pub enum OrderOperand {
+    Number(Option<f64>),
+    String { value: String, number: Option<f64> },
+}
+
+impl ComparisonOperator {
+    pub fn compare<T: PartialOrd + ?Sized>(
+        &self,
+        user_value: &T,
+        feature_value: &T,
+    ) -> bool {
+        match self {
+            ComparisonOperator::Gt => user_value.gt(feature_value),
+            ComparisonOperator::Gte => user_value.ge(feature_value),
+            ComparisonOperator::Lt => user_value.lt(feature_value),
+            ComparisonOperator::Lte => user_value.le(feature_value),
+            ComparisonOperator::Eq => user_value.eq(feature_value),
+            ComparisonOperator::Ne => user_value.ne(feature_value),
+        }
+    }
+}
+
+impl Serialize for Condition {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        self.source.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Condition {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Value::deserialize(deserializer).map(Condition::from)
+    }
+}
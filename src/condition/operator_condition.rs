@@ -1,189 +1,107 @@
-use crate::extensions::FindGrowthBookAttribute;
-use crate::model_public::{GrowthBookAttribute, GrowthBookAttributeValue};
+use crate::model_public::GrowthBookAttributeValue;
 
 pub struct OperatorCondition;
 
 impl OperatorCondition {
-    pub fn not(
-        parent_attribute: Option<&GrowthBookAttribute>,
-        feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
-        recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &[GrowthBookAttribute], bool) -> bool,
-    ) -> bool {
-        match &feature_attribute.value {
-            GrowthBookAttributeValue::Object(it) => it.iter().all(|next| !recursive(parent_attribute, next, user_attributes, false)),
-            _ => false,
-        }
-    }
-
-    pub fn and(
-        _parent_attribute: Option<&GrowthBookAttribute>,
-        feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
-        recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &[GrowthBookAttribute], bool) -> bool,
-    ) -> bool {
-        and_nor(&feature_attribute, user_attributes, recursive, false)
-    }
-
-    pub fn nor(
-        _parent_attribute: Option<&GrowthBookAttribute>,
-        feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
-        recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &[GrowthBookAttribute], bool) -> bool,
-    ) -> bool {
-        and_nor(&feature_attribute, user_attributes, recursive, true)
-    }
-
-    pub fn all(
-        parent_attribute: Option<&GrowthBookAttribute>,
-        feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
-        _recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &[GrowthBookAttribute], bool) -> bool,
-    ) -> bool {
-        match &feature_attribute.value {
-            GrowthBookAttributeValue::Array(feature_values) => {
-                if let Some(GrowthBookAttributeValue::Array(user_values)) = user_attributes.find_value(&parent_attribute.unwrap_or(feature_attribute).key) {
-                    feature_values.iter().all(|feature_item| user_values.iter().any(|user_item| feature_item == user_item))
-                } else {
-                    false
-                }
-            },
-            _ => false,
-        }
-    }
-
     pub fn ne(
-        parent_attribute: Option<&GrowthBookAttribute>,
-        feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
-        _recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &[GrowthBookAttribute], bool) -> bool,
+        user_value: Option<&GrowthBookAttributeValue>,
+        feature_value: &GrowthBookAttributeValue,
     ) -> bool {
-        if let Some(user_value) = user_attributes.find_value(&parent_attribute.unwrap_or(feature_attribute).key) {
-            !match &user_value {
-                GrowthBookAttributeValue::Array(it) => it.iter().any(|item| item == &feature_attribute.value),
+        if let Some(user_value) = user_value {
+            !match user_value {
+                GrowthBookAttributeValue::Array(it) => it.iter().any(|item| item == feature_value),
                 GrowthBookAttributeValue::Empty => true,
-                it => it == &feature_attribute.value,
+                it => it == feature_value,
             }
         } else {
-            true
+            feature_value != &GrowthBookAttributeValue::Null
         }
     }
 
     pub fn eq(
-        parent_attribute: Option<&GrowthBookAttribute>,
-        feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
-        _recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &[GrowthBookAttribute], bool) -> bool,
+        user_value: Option<&GrowthBookAttributeValue>,
+        feature_value: &GrowthBookAttributeValue,
     ) -> bool {
-        if let Some(user_value) = user_attributes.find_value(&parent_attribute.unwrap_or(feature_attribute).key) {
-            match &user_value {
-                GrowthBookAttributeValue::Array(it) => it.iter().any(|item| item == &feature_attribute.value),
+        if let Some(user_value) = user_value {
+            match user_value {
+                GrowthBookAttributeValue::Array(it) => it.iter().any(|item| item == feature_value),
                 GrowthBookAttributeValue::Empty => false,
-                it => it.to_string() == feature_attribute.value.to_string(),
+                GrowthBookAttributeValue::Null => feature_value == &GrowthBookAttributeValue::Null,
+                it => it.to_string() == feature_value.to_string(),
             }
         } else {
-            false
+            feature_value == &GrowthBookAttributeValue::Null
         }
     }
 
     pub fn exists(
-        parent_attribute: Option<&GrowthBookAttribute>,
-        feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
-        _recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &[GrowthBookAttribute], bool) -> bool,
+        user_value: Option<&GrowthBookAttributeValue>,
+        should_exist: bool,
     ) -> bool {
-        if let GrowthBookAttributeValue::Bool(it) = feature_attribute.value {
-            if user_attributes.find_value(&parent_attribute.unwrap_or(feature_attribute).key).is_some() {
-                it
-            } else {
-                !it
-            }
+        let exists = !matches!(user_value, None | Some(GrowthBookAttributeValue::Empty | GrowthBookAttributeValue::Null));
+        exists == should_exist
+    }
+
+    pub fn all(
+        user_value: Option<&GrowthBookAttributeValue>,
+        feature_values: &[GrowthBookAttributeValue],
+        case_insensitive: bool,
+    ) -> bool {
+        if let Some(GrowthBookAttributeValue::Array(user_values)) = user_value {
+            feature_values.iter().all(|feature_item| {
+                user_values
+                    .iter()
+                    .any(|user_item| if case_insensitive { same_value(feature_item, user_item, true) } else { feature_item == user_item })
+            })
         } else {
-            true
+            false
         }
     }
 
     pub fn is_in(
-        parent_attribute: Option<&GrowthBookAttribute>,
-        feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
-        _recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &[GrowthBookAttribute], bool) -> bool,
+        user_value: Option<&GrowthBookAttributeValue>,
+        feature_values: &[GrowthBookAttributeValue],
+        case_insensitive: bool,
     ) -> bool {
-        if let Some(user_value) = user_attributes.find_value(&parent_attribute.unwrap_or(feature_attribute).key) {
-            match &feature_attribute.value {
-                GrowthBookAttributeValue::Array(feature_array) => feature_array.iter().any(|feature_item| match &user_value {
-                    GrowthBookAttributeValue::Array(user_array) => user_array.iter().any(|user_item| feature_item.to_string() == user_item.to_string()),
-                    GrowthBookAttributeValue::Empty => false,
-                    it => feature_item.to_string() == it.to_string(),
-                }),
-                _ => false,
-            }
+        if let Some(user_value) = user_value {
+            feature_values.iter().any(|feature_item| contains(user_value, feature_item, case_insensitive))
         } else {
-            false
+            feature_values.contains(&GrowthBookAttributeValue::Null)
         }
     }
 
     pub fn nin(
-        parent_attribute: Option<&GrowthBookAttribute>,
-        feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
-        _recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &[GrowthBookAttribute], bool) -> bool,
+        user_value: Option<&GrowthBookAttributeValue>,
+        feature_values: &[GrowthBookAttributeValue],
+        case_insensitive: bool,
     ) -> bool {
-        if let Some(user_value) = user_attributes.find_value(&parent_attribute.unwrap_or(feature_attribute).key) {
-            match &feature_attribute.value {
-                GrowthBookAttributeValue::Array(feature_array) => feature_array.iter().all(|feature_item| !match &user_value {
-                    GrowthBookAttributeValue::Array(user_array) => user_array.iter().any(|user_item| feature_item.to_string() == user_item.to_string()),
-                    GrowthBookAttributeValue::Empty => false,
-                    it => feature_item.to_string() == it.to_string(),
-                }),
-                _ => false,
-            }
+        if let Some(user_value) = user_value {
+            feature_values.iter().all(|feature_item| !contains(user_value, feature_item, case_insensitive))
         } else {
-            false
+            !feature_values.contains(&GrowthBookAttributeValue::Null)
         }
     }
+}
 
-    pub fn or(
-        _parent_attribute: Option<&GrowthBookAttribute>,
-        feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
-        recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &[GrowthBookAttribute], bool) -> bool,
-    ) -> bool {
-        match &feature_attribute.value {
-            GrowthBookAttributeValue::Array(it) => {
-                if it.is_empty() {
-                    true
-                } else {
-                    it.iter().any(|next_value| match next_value {
-                        GrowthBookAttributeValue::Object(feature_value) => feature_value.iter().all(|next_attribute| recursive(None, next_attribute, user_attributes, false)),
-                        _ => false,
-                    })
-                }
-            },
-            GrowthBookAttributeValue::Empty => true,
-            _ => false,
-        }
+fn contains(
+    user_value: &GrowthBookAttributeValue,
+    feature_item: &GrowthBookAttributeValue,
+    case_insensitive: bool,
+) -> bool {
+    match user_value {
+        GrowthBookAttributeValue::Array(user_array) => user_array.iter().any(|user_item| same_value(feature_item, user_item, case_insensitive)),
+        GrowthBookAttributeValue::Empty => false,
+        it => same_value(feature_item, it, case_insensitive),
     }
 }
 
-fn and_nor(
-    feature_attribute: &&GrowthBookAttribute,
-    user_attributes: &[GrowthBookAttribute],
-    recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &[GrowthBookAttribute], bool) -> bool,
-    negate: bool,
+fn same_value(
+    feature_item: &GrowthBookAttributeValue,
+    user_item: &GrowthBookAttributeValue,
+    case_insensitive: bool,
 ) -> bool {
-    match &feature_attribute.value {
-        GrowthBookAttributeValue::Array(it) => it.iter().all(|next_value| match next_value {
-            GrowthBookAttributeValue::Object(feature_value) => {
-                let result = feature_value.iter().all(|next_attribute| recursive(None, next_attribute, user_attributes, false));
-                if negate {
-                    !result
-                } else {
-                    result
-                }
-            },
-            _ => false,
-        }),
-        _ => false,
+    match (feature_item, user_item) {
+        (GrowthBookAttributeValue::String(feature_value), GrowthBookAttributeValue::String(user_value)) if case_insensitive => feature_value.to_lowercase() == user_value.to_lowercase(),
+        _ => feature_item.to_string() == user_item.to_string(),
     }
 }
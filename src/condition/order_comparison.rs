@@ -1,148 +1,56 @@
-use crate::extensions::FindGrowthBookAttribute;
-use crate::model_public::{GrowthBookAttribute, GrowthBookAttributeValue};
+use crate::condition::model::{ComparisonOperator, OrderOperand, OrderTarget};
+use crate::model_public::GrowthBookAttributeValue;
 
 pub struct OrderComparison;
 
 impl OrderComparison {
-    pub fn gt(
-        parent_attribute: Option<&GrowthBookAttribute>,
-        feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
-        array_size: bool,
-    ) -> bool {
-        evaluate(
-            parent_attribute,
-            feature_attribute,
-            user_attributes,
-            array_size,
-            |feature_number, user_number| user_number.gt(feature_number),
-            |feature_string, user_string| user_string.gt(feature_string),
-        )
-    }
-
-    pub fn gte(
-        parent_attribute: Option<&GrowthBookAttribute>,
-        feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
-        array_size: bool,
-    ) -> bool {
-        evaluate(
-            parent_attribute,
-            feature_attribute,
-            user_attributes,
-            array_size,
-            |feature_number, user_number| user_number.ge(feature_number),
-            |feature_string, user_string| user_string.ge(feature_string),
-        )
-    }
-
-    pub fn lt(
-        parent_attribute: Option<&GrowthBookAttribute>,
-        feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
-        array_size: bool,
-    ) -> bool {
-        evaluate(
-            parent_attribute,
-            feature_attribute,
-            user_attributes,
-            array_size,
-            |feature_number, user_number| user_number.lt(feature_number),
-            |feature_string, user_string| user_string.lt(feature_string),
-        )
+    pub fn operand(feature_value: &GrowthBookAttributeValue) -> OrderOperand {
+        match feature_value {
+            GrowthBookAttributeValue::String(it) => OrderOperand::String {
+                value: it.clone(),
+                number: feature_value.as_f64(),
+            },
+            it => OrderOperand::Number(it.as_f64()),
+        }
     }
 
-    pub fn lte(
-        parent_attribute: Option<&GrowthBookAttribute>,
-        feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
-        array_size: bool,
+    pub fn matches(
+        user_value: Option<&GrowthBookAttributeValue>,
+        operator: ComparisonOperator,
+        operand: &OrderOperand,
+        target: OrderTarget,
     ) -> bool {
-        evaluate(
-            parent_attribute,
-            feature_attribute,
-            user_attributes,
-            array_size,
-            |feature_number, user_number| user_number.le(feature_number),
-            |feature_string, user_string| user_string.le(feature_string),
-        )
-    }
-}
-
-fn evaluate(
-    parent_attribute: Option<&GrowthBookAttribute>,
-    feature_attribute: &GrowthBookAttribute,
-    user_attributes: &[GrowthBookAttribute],
-    array_size: bool,
-    string_condition: fn(&str, &str) -> bool,
-    number_condition: fn(&f64, &f64) -> bool,
-) -> bool {
-    if feature_attribute.value.is_number() {
-        number_evaluate(parent_attribute, feature_attribute, user_attributes, array_size, number_condition)
-    } else {
-        string_evaluate(parent_attribute, feature_attribute, user_attributes, string_condition)
-    }
-}
-
-fn string_evaluate(
-    parent_attribute: Option<&GrowthBookAttribute>,
-    feature_attribute: &GrowthBookAttribute,
-    user_attributes: &[GrowthBookAttribute],
-    condition: fn(&str, &str) -> bool,
-) -> bool {
-    if let Some(user_value) = user_attributes.find_value(&parent_attribute.unwrap_or(feature_attribute).key) {
-        let feature_value = feature_attribute.value.to_string();
-        match user_value {
-            GrowthBookAttributeValue::Array(it) => it.iter().any(|item| condition(&feature_value, &item.to_string())),
-            it => condition(&feature_value, &it.to_string()),
+        match (user_value, target) {
+            (Some(GrowthBookAttributeValue::Array(it)), OrderTarget::Size) => compare(&GrowthBookAttributeValue::Int(it.len() as i64), operator, operand),
+            (Some(GrowthBookAttributeValue::Array(it)), OrderTarget::Elements) => it.iter().any(|item| compare(item, operator, operand)),
+            // javascript coerces the array to a string like "1,2" which is NaN as a number
+            (Some(GrowthBookAttributeValue::Array(_)), OrderTarget::Value) => false,
+            (Some(it), _) => compare(it, operator, operand),
+            // a missing attribute reads as null, like javascript does
+            (None, _) => compare(&GrowthBookAttributeValue::Null, operator, operand),
         }
-    } else {
-        true
     }
 }
 
-fn number_evaluate(
-    parent_attribute: Option<&GrowthBookAttribute>,
-    feature_attribute: &GrowthBookAttribute,
-    user_attributes: &[GrowthBookAttribute],
-    array_size: bool,
-    condition: fn(&f64, &f64) -> bool,
+// two strings compare lexicographically, anything else is coerced to numbers like javascript does
+fn compare(
+    user_value: &GrowthBookAttributeValue,
+    operator: ComparisonOperator,
+    operand: &OrderOperand,
 ) -> bool {
-    if let Some(user_value) = user_attributes.find_value(&parent_attribute.unwrap_or(feature_attribute).key) {
-        if let (Some(feature_number), Some(user_numbers)) = (get_feature_number(feature_attribute), get_user_numbers(&user_value, array_size)) {
-            user_numbers.iter().any(|number| condition(&feature_number, number))
-        } else {
-            false
-        }
-    } else {
-        true
-    }
-}
-
-fn get_feature_number(feature_attribute: &GrowthBookAttribute) -> Option<f64> {
-    match &feature_attribute.value {
-        GrowthBookAttributeValue::Int(it) => Some(*it as f64),
-        GrowthBookAttributeValue::Float(it) => Some(*it),
-        GrowthBookAttributeValue::String(string_number) => string_number.replace('.', "").parse::<f64>().ok(),
-        _ => None,
+    match (user_value, operand) {
+        (GrowthBookAttributeValue::String(user_string), OrderOperand::String { value, .. }) => operator.compare(user_string.as_str(), value.as_str()),
+        (user_value, OrderOperand::String { number, .. } | OrderOperand::Number(number)) => match (user_number(user_value), number) {
+            (Some(user_number), Some(feature_number)) => operator.compare(&user_number, feature_number),
+            _ => false,
+        },
     }
 }
 
-fn get_user_numbers(
-    user_value: &GrowthBookAttributeValue,
-    array_size: bool,
-) -> Option<Vec<f64>> {
+// javascript coerces null to 0
+fn user_number(user_value: &GrowthBookAttributeValue) -> Option<f64> {
     match user_value {
-        GrowthBookAttributeValue::Int(it) => Some(vec![*it as f64]),
-        GrowthBookAttributeValue::Float(it) => Some(vec![*it]),
-        GrowthBookAttributeValue::Array(it) => {
-            if array_size {
-                Some(vec![it.len() as f64])
-            } else {
-                Some(it.iter().filter(|item| item.is_number()).map(|item| item.as_f64().expect("Failed to convert to f64")).collect())
-            }
-        },
-        GrowthBookAttributeValue::String(string_number) => string_number.replace('.', "").parse::<f64>().ok().map(|it| vec![it]),
-        _ => None,
+        GrowthBookAttributeValue::Null => Some(0.0),
+        it => it.as_f64(),
     }
 }
@@ -1,31 +1,32 @@
-use regex::Regex;
+use regex::{Regex, RegexBuilder};
+use tracing::error;
 
-use crate::extensions::FindGrowthBookAttribute;
-use crate::model_public::{GrowthBookAttribute, GrowthBookAttributeValue};
+use crate::model_public::GrowthBookAttributeValue;
 
 pub struct RegexComparison;
 
 impl RegexComparison {
+    pub fn compile(
+        pattern: &str,
+        case_insensitive: bool,
+    ) -> Option<Regex> {
+        match RegexBuilder::new(pattern).case_insensitive(case_insensitive).build() {
+            Ok(regex) => Some(regex),
+            Err(e) => {
+                error!("[growthbook-sdk] invalid regex '{pattern}' in condition, it will never match: {e}");
+                None
+            },
+        }
+    }
+
     pub fn matches(
-        parent_attribute: Option<&GrowthBookAttribute>,
-        feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
+        user_value: Option<&GrowthBookAttributeValue>,
+        regex: &Regex,
     ) -> bool {
-        if let GrowthBookAttributeValue::String(feature_value) = &feature_attribute.value {
-            if let Ok(regex) = Regex::new(feature_value) {
-                if let Some(user_value) = user_attributes.find_value(&parent_attribute.unwrap_or(feature_attribute).key) {
-                    match &user_value {
-                        GrowthBookAttributeValue::Array(it) => it.iter().any(|item| regex.is_match(&item.to_string())),
-                        it => regex.is_match(&it.to_string()),
-                    }
-                } else {
-                    false
-                }
-            } else {
-                false
-            }
-        } else {
-            true
+        match user_value {
+            Some(GrowthBookAttributeValue::Array(it)) => it.iter().any(|item| regex.is_match(&item.to_string())),
+            Some(it) => regex.is_match(&it.to_string()),
+            None => false,
         }
     }
 }
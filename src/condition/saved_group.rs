@@ -0,0 +1,65 @@
+use std::collections::{HashMap, HashSet};
+
+use serde_json::Value;
+
+use crate::model_public::GrowthBookAttributeValue;
+
+#[derive(Clone, Debug, Default)]
+pub struct SavedGroups {
+    groups: HashMap<String, HashSet<String>>,
+}
+
+impl SavedGroups {
+    pub fn new(saved_groups: HashMap<String, Vec<Value>>) -> Self {
+        let groups = saved_groups
+            .into_iter()
+            .map(|(group_id, members)| (group_id, members.into_iter().map(|member| member_key(&GrowthBookAttributeValue::from(member))).collect()))
+            .collect();
+        SavedGroups { groups }
+    }
+
+    pub fn contains(
+        &self,
+        group_id: &str,
+        value: &GrowthBookAttributeValue,
+    ) -> bool {
+        match self.groups.get(group_id) {
+            Some(members) => match value {
+                GrowthBookAttributeValue::Array(it) => it.iter().any(|item| members.contains(&member_key(item))),
+                it => members.contains(&member_key(it)),
+            },
+            None => false,
+        }
+    }
+}
+
+pub struct SavedGroupComparison;
+
+impl SavedGroupComparison {
+    pub fn in_group(
+        user_value: Option<&GrowthBookAttributeValue>,
+        group_id: &str,
+        saved_groups: &SavedGroups,
+    ) -> bool {
+        user_value.map(|user_value| saved_groups.contains(group_id, user_value)).unwrap_or(false)
+    }
+
+    pub fn not_in_group(
+        user_value: Option<&GrowthBookAttributeValue>,
+        group_id: &str,
+        saved_groups: &SavedGroups,
+    ) -> bool {
+        !Self::in_group(user_value, group_id, saved_groups)
+    }
+}
+
+fn member_key(value: &GrowthBookAttributeValue) -> String {
+    match value {
+        GrowthBookAttributeValue::String(it) => format!("s:{it}"),
+        GrowthBookAttributeValue::Int(it) => format!("n:{it}"),
+        GrowthBookAttributeValue::Float(it) if it.fract() == 0.0 && it.abs() < i64::MAX as f64 => format!("n:{}", *it as i64),
+        GrowthBookAttributeValue::Float(it) => format!("n:{it}"),
+        GrowthBookAttributeValue::Bool(it) => format!("b:{it}"),
+        it => format!("j:{}", it.to_value()),
+    }
+}
@@ -1,25 +1,16 @@
-use crate::extensions::FindGrowthBookAttribute;
-use crate::model_public::{GrowthBookAttribute, GrowthBookAttributeValue};
+use crate::model_public::GrowthBookAttributeValue;
 
 pub struct SizeComparison;
 
 impl SizeComparison {
     pub fn matches(
-        parent_attribute: Option<&GrowthBookAttribute>,
-        feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
-        recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &[GrowthBookAttribute], bool) -> bool,
+        user_value: Option<&GrowthBookAttributeValue>,
+        feature_size: i64,
     ) -> bool {
-        match &feature_attribute.value {
-            GrowthBookAttributeValue::Int(feature_value) => {
-                if let Some(GrowthBookAttributeValue::Array(user_value)) = user_attributes.find_value(&parent_attribute.unwrap_or(feature_attribute).key) {
-                    feature_value == &(user_value.len() as i64)
-                } else {
-                    false
-                }
-            },
-            GrowthBookAttributeValue::Object(feature_value) => feature_value.iter().all(|next| recursive(parent_attribute, next, user_attributes, true)),
-            _ => false,
+        if let Some(GrowthBookAttributeValue::Array(user_value)) = user_value {
+            feature_size == user_value.len() as i64
+        } else {
+            false
         }
     }
 }
@@ -1,36 +1,25 @@
-use crate::extensions::FindGrowthBookAttribute;
-use crate::model_public::{GrowthBookAttribute, GrowthBookAttributeValue};
+use crate::model_public::GrowthBookAttributeValue;
 
 pub struct TypeComparison;
 
 impl TypeComparison {
     pub fn matches(
-        parent_attribute: Option<&GrowthBookAttribute>,
-        feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
+        user_value: Option<&GrowthBookAttributeValue>,
+        feature_type: &str,
     ) -> bool {
-        if let GrowthBookAttributeValue::String(feature_type) = &feature_attribute.value {
-            if let Some(user_value) = user_attributes.find_value(&parent_attribute.unwrap_or(feature_attribute).key) {
-                match user_value {
-                    GrowthBookAttributeValue::String(_) => feature_type == "string",
-                    GrowthBookAttributeValue::Int(_) => feature_type == "number",
-                    GrowthBookAttributeValue::Float(_) => feature_type == "number",
-                    GrowthBookAttributeValue::Bool(_) => feature_type == "boolean",
-                    GrowthBookAttributeValue::Array(_) => feature_type == "array",
-                    GrowthBookAttributeValue::Object(it) => {
-                        if it.is_empty() {
-                            feature_type == "null"
-                        } else {
-                            feature_type == "object"
-                        }
-                    },
-                    GrowthBookAttributeValue::Empty => feature_type == "null",
-                }
-            } else {
-                feature_type == "null"
+        if let Some(user_value) = user_value {
+            match user_value {
+                GrowthBookAttributeValue::String(_) => feature_type == "string",
+                GrowthBookAttributeValue::Int(_) => feature_type == "number",
+                GrowthBookAttributeValue::Float(_) => feature_type == "number",
+                GrowthBookAttributeValue::Bool(_) => feature_type == "boolean",
+                GrowthBookAttributeValue::Array(_) => feature_type == "array",
+                GrowthBookAttributeValue::Object(_) => feature_type == "object",
+                GrowthBookAttributeValue::Null => feature_type == "null",
+                GrowthBookAttributeValue::Empty => feature_type == "undefined",
             }
         } else {
-            false
+            feature_type == "null"
         }
     }
 }
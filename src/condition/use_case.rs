@@ -1,166 +1,244 @@
-use crate::condition::elem_match_comparison::ElemMatchComparison;
+use std::sync::Arc;
+
+use serde_json::Value;
+
+use crate::condition::model::{Comparison, ComparisonOperator, Condition, ConditionNode, OrderTarget};
 use crate::condition::operator_condition::OperatorCondition;
 use crate::condition::order_comparison::OrderComparison;
 use crate::condition::regex_comparison::RegexComparison;
+use crate::condition::saved_group::{SavedGroupComparison, SavedGroups};
 use crate::condition::size_comparison::SizeComparison;
 use crate::condition::type_comparison::TypeComparison;
-use crate::condition::version_comparison::VersionComparison;
+use crate::condition::version_comparison::{normalize, VersionComparison};
 use crate::extensions::FindGrowthBookAttribute;
 use crate::model_public::{GrowthBookAttribute, GrowthBookAttributeValue};
 
-pub trait ConditionsMatchesAttributes {
-    fn matches(
+impl Condition {
+    pub fn matches(
         &self,
         user_attributes: &[GrowthBookAttribute],
-    ) -> bool;
+        saved_groups: &SavedGroups,
+    ) -> bool {
+        all(&self.nodes, user_attributes, saved_groups)
+    }
 }
 
-impl ConditionsMatchesAttributes for Vec<GrowthBookAttribute> {
-    fn matches(
-        &self,
-        user_attributes: &[GrowthBookAttribute],
-    ) -> bool {
-        self.iter().all(|it| verify(None, it, user_attributes, false))
+impl From<Value> for Condition {
+    fn from(value: Value) -> Self {
+        let nodes = match GrowthBookAttribute::from(value.clone()) {
+            Ok(attributes) => compile_all(&attributes, None, OrderTarget::Value),
+            Err(_) => vec![ConditionNode::Constant(false)],
+        };
+        Condition {
+            source: Arc::new(value),
+            nodes: Arc::new(nodes),
+        }
     }
 }
 
-fn verify(
-    parent_attribute: Option<&GrowthBookAttribute>,
+fn compile_all(
+    feature_attributes: &[GrowthBookAttribute],
+    parent_path: Option<&str>,
+    target: OrderTarget,
+) -> Vec<ConditionNode> {
+    feature_attributes.iter().map(|it| compile(it, parent_path, target)).collect()
+}
+
+fn compile(
     feature_attribute: &GrowthBookAttribute,
-    user_attributes: &[GrowthBookAttribute],
-    array_size: bool,
-) -> bool {
+    parent_path: Option<&str>,
+    target: OrderTarget,
+) -> ConditionNode {
+    let path = parent_path.unwrap_or(&feature_attribute.key);
+    let value = &feature_attribute.value;
+    let attribute = |comparison: Comparison| ConditionNode::Attribute { path: String::from(path), comparison };
     match feature_attribute.key.as_str() {
-        "$not" => OperatorCondition::not(parent_attribute, feature_attribute, user_attributes, verify),
-        "$ne" => OperatorCondition::ne(parent_attribute, feature_attribute, user_attributes, verify),
-        "$and" => OperatorCondition::and(parent_attribute, feature_attribute, user_attributes, verify),
-        "$nor" => OperatorCondition::nor(parent_attribute, feature_attribute, user_attributes, verify),
-        "$or" => OperatorCondition::or(parent_attribute, feature_attribute, user_attributes, verify),
-        "$in" => OperatorCondition::is_in(parent_attribute, feature_attribute, user_attributes, verify),
-        "$nin" => OperatorCondition::nin(parent_attribute, feature_attribute, user_attributes, verify),
-        "$gt" => OrderComparison::gt(parent_attribute, feature_attribute, user_attributes, array_size),
-        "$gte" => OrderComparison::gte(parent_attribute, feature_attribute, user_attributes, array_size),
-        "$lt" => OrderComparison::lt(parent_attribute, feature_attribute, user_attributes, array_size),
-        "$lte" => OrderComparison::lte(parent_attribute, feature_attribute, user_attributes, array_size),
-        "$eq" => OperatorCondition::eq(parent_attribute, feature_attribute, user_attributes, verify),
-        "$exists" => OperatorCondition::exists(parent_attribute, feature_attribute, user_attributes, verify),
-        "$regex" => RegexComparison::matches(parent_attribute, feature_attribute, user_attributes),
-        "$type" => TypeComparison::matches(parent_attribute, feature_attribute, user_attributes),
-        "$size" => SizeComparison::matches(parent_attribute, feature_attribute, user_attributes, verify),
-        "$all" => OperatorCondition::all(parent_attribute, feature_attribute, user_attributes, verify),
-        "$vgt" => VersionComparison::vgt(parent_attribute, feature_attribute, user_attributes),
-        "$vgte" => VersionComparison::vgte(parent_attribute, feature_attribute, user_attributes),
-        "$vlt" => VersionComparison::vlt(parent_attribute, feature_attribute, user_attributes),
-        "$vlte" => VersionComparison::vlte(parent_attribute, feature_attribute, user_attributes),
-        "$veq" => VersionComparison::veq(parent_attribute, feature_attribute, user_attributes),
-        "$vne" => VersionComparison::vne(parent_attribute, feature_attribute, user_attributes),
-        "$elemMatch" => ElemMatchComparison::matches(parent_attribute, feature_attribute, user_attributes, array_size, verify),
-        _ => non_operator_or_condition(parent_attribute, feature_attribute, user_attributes),
+        "$not" => match value {
+            GrowthBookAttributeValue::Object(it) => ConditionNode::Not(compile_all(it, parent_path, OrderTarget::Value)),
+            _ => ConditionNode::Constant(false),
+        },
+        "$and" => clauses(value).map(ConditionNode::And).unwrap_or(ConditionNode::Constant(false)),
+        "$nor" => clauses(value).map(ConditionNode::Nor).unwrap_or(ConditionNode::Constant(false)),
+        "$or" => match value {
+            GrowthBookAttributeValue::Array(it) => ConditionNode::Or(
+                it.iter()
+                    .map(|item| match item {
+                        GrowthBookAttributeValue::Object(feature_value) => compile_all(feature_value, None, OrderTarget::Value),
+                        _ => vec![ConditionNode::Constant(false)],
+                    })
+                    .collect(),
+            ),
+            GrowthBookAttributeValue::Null | GrowthBookAttributeValue::Empty => ConditionNode::Constant(true),
+            _ => ConditionNode::Constant(false),
+        },
+        "$eq" => attribute(Comparison::Eq(value.clone())),
+        "$ne" => attribute(Comparison::Ne(value.clone())),
+        "$exists" => match value {
+            GrowthBookAttributeValue::Bool(it) => attribute(Comparison::Exists(*it)),
+            _ => ConditionNode::Constant(true),
+        },
+        "$in" | "$ini" | "$nin" | "$nini" | "$all" | "$alli" => match value {
+            GrowthBookAttributeValue::Array(it) => {
+                let values = it.clone();
+                let case_insensitive = feature_attribute.key.ends_with('i');
+                attribute(match feature_attribute.key.trim_end_matches('i') {
+                    "$in" => Comparison::In { values, case_insensitive },
+                    "$nin" => Comparison::Nin { values, case_insensitive },
+                    _ => Comparison::All { values, case_insensitive },
+                })
+            },
+            _ => ConditionNode::Constant(false),
+        },
+        "$gt" | "$gte" | "$lt" | "$lte" => attribute(Comparison::Order {
+            operator: operator(&feature_attribute.key[1..]),
+            operand: OrderComparison::operand(value),
+            target,
+        }),
+        "$vgt" | "$vgte" | "$vlt" | "$vlte" | "$veq" | "$vne" => attribute(Comparison::Version {
+            operator: operator(&feature_attribute.key[2..]),
+            version: normalize(&value.to_string()),
+        }),
+        "$regex" | "$regexi" => match value {
+            GrowthBookAttributeValue::String(pattern) => match RegexComparison::compile(pattern, feature_attribute.key == "$regexi") {
+                Some(regex) => attribute(Comparison::Regex(regex)),
+                None => ConditionNode::Constant(false),
+            },
+            _ => ConditionNode::Constant(true),
+        },
+        "$type" => match value {
+            GrowthBookAttributeValue::String(it) => attribute(Comparison::Type(it.clone())),
+            _ => ConditionNode::Constant(false),
+        },
+        "$size" => match value {
+            GrowthBookAttributeValue::Int(it) => attribute(Comparison::Size(*it)),
+            GrowthBookAttributeValue::Object(it) => ConditionNode::All(compile_all(it, parent_path, OrderTarget::Size)),
+            _ => ConditionNode::Constant(false),
+        },
+        "$elemMatch" => match value {
+            GrowthBookAttributeValue::Object(it) => ConditionNode::Any(compile_all(it, parent_path, if target == OrderTarget::Size { target } else { OrderTarget::Elements })),
+            _ => ConditionNode::Constant(false),
+        },
+        "$inGroup" | "$notInGroup" => match value {
+            GrowthBookAttributeValue::String(it) if feature_attribute.key == "$inGroup" => attribute(Comparison::InGroup(it.clone())),
+            GrowthBookAttributeValue::String(it) => attribute(Comparison::NotInGroup(it.clone())),
+            _ => ConditionNode::Constant(false),
+        },
+        key => match value {
+            GrowthBookAttributeValue::String(_) if key.starts_with('$') => ConditionNode::Constant(false),
+            GrowthBookAttributeValue::String(_) => attribute(Comparison::Eq(value.clone())),
+            GrowthBookAttributeValue::Array(it) => attribute(Comparison::ArrayEquals(it.clone())),
+            GrowthBookAttributeValue::Object(it) if !it.is_empty() => {
+                let nested_path = parent_path.map(|parent| format!("{parent}.{key}")).unwrap_or(String::from(key));
+                ConditionNode::All(compile_all(it, Some(&nested_path), OrderTarget::Value))
+            },
+            GrowthBookAttributeValue::Null | GrowthBookAttributeValue::Empty => attribute(Comparison::Null),
+            it => attribute(Comparison::Equals(it.clone())),
+        },
     }
 }
 
-fn non_operator_or_condition(
-    parent_attribute: Option<&GrowthBookAttribute>,
-    feature_attribute: &GrowthBookAttribute,
-    user_attributes: &[GrowthBookAttribute],
-) -> bool {
-    match &feature_attribute.value {
-        GrowthBookAttributeValue::String(_) => string_non_operator(parent_attribute, feature_attribute, user_attributes),
-        GrowthBookAttributeValue::Array(feature_values) => array(&parent_attribute, &feature_attribute, user_attributes, feature_values),
-        GrowthBookAttributeValue::Object(it) => object(parent_attribute, feature_attribute, user_attributes, it),
-        GrowthBookAttributeValue::Empty => empty(&parent_attribute, &feature_attribute, user_attributes),
-        it => fallback(&parent_attribute, feature_attribute, user_attributes, it),
+fn clauses(value: &GrowthBookAttributeValue) -> Option<Vec<Vec<ConditionNode>>> {
+    match value {
+        GrowthBookAttributeValue::Array(it) => it
+            .iter()
+            .map(|item| match item {
+                GrowthBookAttributeValue::Object(feature_value) => Some(compile_all(feature_value, None, OrderTarget::Value)),
+                _ => None,
+            })
+            .collect(),
+        _ => None,
     }
 }
 
-fn string_non_operator(
-    parent_attribute: Option<&GrowthBookAttribute>,
-    feature_attribute: &GrowthBookAttribute,
-    user_attributes: &[GrowthBookAttribute],
-) -> bool {
-    if feature_attribute.key.starts_with('$') {
-        false
-    } else {
-        OperatorCondition::eq(parent_attribute, feature_attribute, user_attributes, verify)
+fn operator(name: &str) -> ComparisonOperator {
+    match name {
+        "gt" => ComparisonOperator::Gt,
+        "gte" => ComparisonOperator::Gte,
+        "lt" => ComparisonOperator::Lt,
+        "lte" => ComparisonOperator::Lte,
+        "eq" => ComparisonOperator::Eq,
+        _ => ComparisonOperator::Ne,
     }
 }
 
-fn array(
-    parent_attribute: &Option<&GrowthBookAttribute>,
-    feature_attribute: &&GrowthBookAttribute,
+fn all(
+    nodes: &[ConditionNode],
     user_attributes: &[GrowthBookAttribute],
-    feature_values: &[GrowthBookAttributeValue],
+    saved_groups: &SavedGroups,
 ) -> bool {
-    if let Some(GrowthBookAttributeValue::Array(user_values)) = user_attributes.find_value(&parent_attribute.unwrap_or(feature_attribute).key) {
-        if feature_values.len() == user_values.len() {
-            feature_values.iter().enumerate().all(|(index, value)| value == &user_values[index])
-        } else {
-            false
-        }
-    } else {
-        false
-    }
+    nodes.iter().all(|it| verify(it, user_attributes, saved_groups))
 }
 
-fn object(
-    parent_attribute: Option<&GrowthBookAttribute>,
-    feature_attribute: &GrowthBookAttribute,
+fn verify(
+    node: &ConditionNode,
     user_attributes: &[GrowthBookAttribute],
-    it: &[GrowthBookAttribute],
+    saved_groups: &SavedGroups,
 ) -> bool {
-    if it.is_empty() {
-        user_attributes.find_value(&parent_attribute.unwrap_or(feature_attribute).key).is_none()
-    } else {
-        it.iter().all(|next| {
-            let parent = feature_attribute.aggregate_key(parent_attribute);
-            verify(Some(&parent), next, user_attributes, false)
-        })
+    match node {
+        ConditionNode::Constant(it) => *it,
+        ConditionNode::Not(nodes) => !all(nodes, user_attributes, saved_groups),
+        ConditionNode::And(clauses) => clauses.iter().all(|it| all(it, user_attributes, saved_groups)),
+        ConditionNode::Nor(clauses) => clauses.iter().all(|it| !all(it, user_attributes, saved_groups)),
+        ConditionNode::Or(clauses) => clauses.is_empty() || clauses.iter().any(|it| all(it, user_attributes, saved_groups)),
+        ConditionNode::All(nodes) => all(nodes, user_attributes, saved_groups),
+        ConditionNode::Any(nodes) => nodes.iter().any(|it| verify(it, user_attributes, saved_groups)),
+        ConditionNode::Attribute { path, comparison } => {
+            let user_value = user_attributes.find_value(path);
+            compare(comparison, user_value.as_ref(), saved_groups)
+        },
     }
 }
 
-fn empty(
-    parent_attribute: &Option<&GrowthBookAttribute>,
-    feature_attribute: &&GrowthBookAttribute,
-    user_attributes: &[GrowthBookAttribute],
+fn compare(
+    comparison: &Comparison,
+    user_value: Option<&GrowthBookAttributeValue>,
+    saved_groups: &SavedGroups,
 ) -> bool {
-    if let Some(it) = user_attributes.find_value(&parent_attribute.unwrap_or(feature_attribute).key) {
-        it == GrowthBookAttributeValue::Empty
-    } else {
-        true
+    match comparison {
+        Comparison::Eq(it) => OperatorCondition::eq(user_value, it),
+        Comparison::Ne(it) => OperatorCondition::ne(user_value, it),
+        Comparison::Exists(it) => OperatorCondition::exists(user_value, *it),
+        Comparison::In { values, case_insensitive } => OperatorCondition::is_in(user_value, values, *case_insensitive),
+        Comparison::Nin { values, case_insensitive } => OperatorCondition::nin(user_value, values, *case_insensitive),
+        Comparison::All { values, case_insensitive } => OperatorCondition::all(user_value, values, *case_insensitive),
+        Comparison::Order { operator, operand, target } => OrderComparison::matches(user_value, *operator, operand, *target),
+        Comparison::Version { operator, version } => VersionComparison::matches(user_value, *operator, version),
+        Comparison::Regex(regex) => RegexComparison::matches(user_value, regex),
+        Comparison::Type(it) => TypeComparison::matches(user_value, it),
+        Comparison::Size(it) => SizeComparison::matches(user_value, *it),
+        Comparison::InGroup(it) => SavedGroupComparison::in_group(user_value, it, saved_groups),
+        Comparison::NotInGroup(it) => SavedGroupComparison::not_in_group(user_value, it, saved_groups),
+        Comparison::ArrayEquals(feature_values) => array(user_value, feature_values),
+        Comparison::Null => matches!(user_value, None | Some(GrowthBookAttributeValue::Null)),
+        Comparison::Equals(it) => user_value.map(|user_value| it == user_value).unwrap_or(false),
     }
 }
 
-fn fallback(
-    parent_attribute: &Option<&GrowthBookAttribute>,
-    feature_attribute: &GrowthBookAttribute,
-    user_attributes: &[GrowthBookAttribute],
-    it: &GrowthBookAttributeValue,
+fn array(
+    user_value: Option<&GrowthBookAttributeValue>,
+    feature_values: &[GrowthBookAttributeValue],
 ) -> bool {
-    if let Some(user_value) = user_attributes.find_value(&parent_attribute.unwrap_or(feature_attribute).key) {
-        it == &user_value
+    if let Some(GrowthBookAttributeValue::Array(user_values)) = user_value {
+        if feature_values.len() == user_values.len() {
+            feature_values.iter().enumerate().all(|(index, value)| value == &user_values[index])
+        } else {
+            false
+        }
     } else {
         false
     }
 }
 
-impl GrowthBookAttribute {
-    fn aggregate_key(
-        &self,
-        parent_attribute: Option<&GrowthBookAttribute>,
-    ) -> Self {
-        let key = parent_attribute.map(|parent| format!("{}.{}", parent.key, self.key)).unwrap_or(self.key.clone());
-        GrowthBookAttribute { key, value: self.value.clone() }
-    }
-}
-
 #[cfg(test)]
 mod test {
+    use std::collections::HashMap;
     use std::fs;
 
     use serde::Deserialize;
     use serde_json::Value;
 
-    use crate::condition::use_case::ConditionsMatchesAttributes;
+    use crate::condition::model::Condition;
+    use crate::condition::saved_group::SavedGroups;
     use crate::model_public::GrowthBookAttribute;
 
     #[tokio::test]
@@ -169,9 +247,9 @@ mod test {
 
         for value in cases.eval_condition {
             let eval_condition = EvalCondition::new(value);
-            let vec_condition = &GrowthBookAttribute::from(eval_condition.condition).expect("Failed to create attributes");
+            let condition = Condition::from(eval_condition.condition);
             let vec_attributes = GrowthBookAttribute::from(eval_condition.attribute).expect("Failed to create attributes");
-            let enabled = vec_condition.matches(&vec_attributes);
+            let enabled = condition.matches(&vec_attributes, &eval_condition.saved_groups);
             if enabled != eval_condition.result {
                 panic!("EvalCondition failed: {}", eval_condition.name)
             }
@@ -191,6 +269,7 @@ mod test {
         condition: Value,
         attribute: Value,
         result: bool,
+        saved_groups: SavedGroups,
     }
 
     impl EvalCondition {
@@ -201,6 +280,12 @@ mod test {
                 condition: array[1].clone(),
                 attribute: array[2].clone(),
                 result: array[3].as_bool().expect("Failed to convert to bool"),
+                saved_groups: SavedGroups::new(
+                    array
+                        .get(4)
+                        .map(|it| serde_json::from_value::<HashMap<String, Vec<Value>>>(it.clone()).expect("Failed to convert to saved groups"))
+                        .unwrap_or_default(),
+                ),
             }
         }
     }
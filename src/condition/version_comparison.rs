@@ -1,96 +1,43 @@
+use lazy_static::lazy_static;
 use regex::Regex;
 
-use crate::extensions::FindGrowthBookAttribute;
-use crate::model_public::{GrowthBookAttribute, GrowthBookAttributeValue};
+use crate::condition::model::ComparisonOperator;
+use crate::model_public::GrowthBookAttributeValue;
+
+lazy_static! {
+    static ref PREFIX_AND_BUILD: Regex = Regex::new("(^v|\\+.*$)").expect("Failed to compile version prefix regex");
+    static ref SEPARATORS: Regex = Regex::new("[-.]").expect("Failed to compile version separator regex");
+    static ref LEADING_DIGITS: Regex = Regex::new("^\\d+").expect("Failed to compile version digits regex");
+}
 
 pub struct VersionComparison;
 
 impl VersionComparison {
-    pub fn vgt(
-        parent_attribute: Option<&GrowthBookAttribute>,
-        feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
-    ) -> bool {
-        evaluate(parent_attribute, feature_attribute, user_attributes, |feature_version, user_version| user_version.gt(feature_version))
-    }
-
-    pub fn vgte(
-        parent_attribute: Option<&GrowthBookAttribute>,
-        feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
-    ) -> bool {
-        evaluate(parent_attribute, feature_attribute, user_attributes, |feature_version, user_version| user_version.ge(feature_version))
-    }
-
-    pub fn vlt(
-        parent_attribute: Option<&GrowthBookAttribute>,
-        feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
-    ) -> bool {
-        evaluate(parent_attribute, feature_attribute, user_attributes, |feature_version, user_version| user_version.lt(feature_version))
-    }
-
-    pub fn vlte(
-        parent_attribute: Option<&GrowthBookAttribute>,
-        feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
+    pub fn matches(
+        user_value: Option<&GrowthBookAttributeValue>,
+        operator: ComparisonOperator,
+        feature_version: &str,
     ) -> bool {
-        evaluate(parent_attribute, feature_attribute, user_attributes, |feature_version, user_version| user_version.le(feature_version))
-    }
-
-    pub fn veq(
-        parent_attribute: Option<&GrowthBookAttribute>,
-        feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
-    ) -> bool {
-        evaluate(parent_attribute, feature_attribute, user_attributes, |feature_version, user_version| user_version.eq(feature_version))
-    }
-
-    pub fn vne(
-        parent_attribute: Option<&GrowthBookAttribute>,
-        feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
-    ) -> bool {
-        evaluate(parent_attribute, feature_attribute, user_attributes, |feature_version, user_version| user_version.ne(feature_version))
+        // javascript compares anything that is not a string or a number as version "0"
+        let user_version = match user_value {
+            Some(GrowthBookAttributeValue::String(it)) => it.clone(),
+            Some(it @ (GrowthBookAttributeValue::Int(_) | GrowthBookAttributeValue::Float(_))) => it.to_string(),
+            _ => String::from("0"),
+        };
+        operator.compare(normalize(&user_version).as_str(), feature_version)
     }
 }
 
-fn evaluate(
-    parent_attribute: Option<&GrowthBookAttribute>,
-    feature_attribute: &GrowthBookAttribute,
-    user_attributes: &[GrowthBookAttribute],
-    condition: fn(&str, &str) -> bool,
-) -> bool {
-    if let Some(GrowthBookAttributeValue::String(user_version)) = user_attributes.find_value(&parent_attribute.unwrap_or(feature_attribute).key) {
-        let feature_version = feature_attribute.value.to_string();
-        condition(&normalize(&feature_version), &normalize(&user_version))
-    } else {
-        true
-    }
-}
-
-fn normalize(version: &str) -> String {
-    if let Ok(regex1) = Regex::new("(^v|\\+.*$)") {
-        if let Ok(regex2) = Regex::new("[-.]") {
-            if let Ok(regex3) = Regex::new("^\\d+") {
-                let string = regex1.replace_all(version, "").to_string();
-                let mut split = regex2.split(&string).filter(|item| !item.is_empty()).collect::<Vec<&str>>();
-                if split.len() == 3 {
-                    split.push("~");
-                }
-                split
-                    .iter()
-                    .map(|part| if regex3.is_match(part) { format!("{:0>5}", part) } else { part.to_string() })
-                    .filter(|part| !part.is_empty())
-                    .reduce(|a, b| format!("{a}-{b}"))
-                    .unwrap_or(version.to_string())
-            } else {
-                version.to_string()
-            }
-        } else {
-            version.to_string()
-        }
-    } else {
-        version.to_string()
-    }
+pub fn normalize(version: &str) -> String {
+    let string = PREFIX_AND_BUILD.replace_all(version, "").to_string();
+    let mut split = SEPARATORS.split(&string).filter(|item| !item.is_empty()).collect::<Vec<&str>>();
+    if split.len() == 3 {
+        split.push("~");
+    }
+    split
+        .iter()
+        .map(|part| if LEADING_DIGITS.is_match(part) { format!("{:0>5}", part) } else { part.to_string() })
+        .filter(|part| !part.is_empty())
+        .reduce(|a, b| format!("{a}-{b}"))
+        .unwrap_or(version.to_string())
 }
@@ -0,0 +1,109 @@
+use aes::cipher::block_padding::Pkcs7;
+use aes::cipher::{BlockDecryptMut, KeyIvInit};
+use aes::{Aes128, Aes256};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+use crate::error::{GrowthbookError, GrowthbookErrorCode};
+
+type Aes128CbcDec = cbc::Decryptor<Aes128>;
+type Aes256CbcDec = cbc::Decryptor<Aes256>;
+
+pub struct Decryption;
+
+impl Decryption {
+    pub fn decrypt(
+        encrypted: &str,
+        decryption_key: &str,
+    ) -> Result<String, GrowthbookError> {
+        let (encoded_iv, encoded_cipher) = encrypted
+            .split_once('.')
+            .ok_or_else(|| GrowthbookError::new(GrowthbookErrorCode::DecryptionError, "Encrypted payload must be in the format 'iv.ciphertext'"))?;
+
+        let key = decode(decryption_key, "decryption key")?;
+        let iv = decode(encoded_iv, "iv")?;
+        let cipher = decode(encoded_cipher, "ciphertext")?;
+
+        let decrypted = match key.len() {
+            16 => Aes128CbcDec::new_from_slices(&key, &iv).map(|decryptor| decryptor.decrypt_padded_vec_mut::<Pkcs7>(&cipher)),
+            32 => Aes256CbcDec::new_from_slices(&key, &iv).map(|decryptor| decryptor.decrypt_padded_vec_mut::<Pkcs7>(&cipher)),
+            length => {
+                return Err(GrowthbookError::new(
+                    GrowthbookErrorCode::DecryptionError,
+                    &format!("Invalid decryption key length={length}, expected 16 or 32 bytes"),
+                ))
+            },
+        }
+        .map_err(|_| GrowthbookError::new(GrowthbookErrorCode::DecryptionError, "Invalid iv length, expected 16 bytes"))?
+        .map_err(|_| GrowthbookError::new(GrowthbookErrorCode::DecryptionError, "Failed to decrypt payload, check the decryption key"))?;
+
+        String::from_utf8(decrypted).map_err(|_| GrowthbookError::new(GrowthbookErrorCode::DecryptionError, "Decrypted payload is not a valid utf-8 string"))
+    }
+}
+
+fn decode(
+    value: &str,
+    name: &str,
+) -> Result<Vec<u8>, GrowthbookError> {
+    STANDARD
+        .decode(value)
+        .map_err(|e| GrowthbookError::new(GrowthbookErrorCode::DecryptionError, &format!("Failed to decode {name} from base64: {e}")))
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use serde::Deserialize;
+    use serde_json::Value;
+
+    use crate::decryption::Decryption;
+
+    #[tokio::test]
+    async fn evaluate_decrypt() -> Result<(), Box<dyn std::error::Error>> {
+        let cases = Cases::new();
+
+        for value in cases.decrypt {
+            let eval_decrypt = EvalDecrypt::new(value);
+            let result = Decryption::decrypt(&eval_decrypt.encrypted, &eval_decrypt.key).ok();
+            if result != eval_decrypt.result {
+                panic!("EvalDecrypt failed: name='{}' expected={:?} result={:?}", eval_decrypt.name, eval_decrypt.result, result)
+            }
+        }
+
+        Ok(())
+    }
+
+    #[derive(Deserialize, Clone)]
+    #[serde(rename_all = "camelCase")]
+    struct Cases {
+        decrypt: Vec<Value>,
+    }
+
+    pub struct EvalDecrypt {
+        name: String,
+        encrypted: String,
+        key: String,
+        result: Option<String>,
+    }
+
+    impl EvalDecrypt {
+        fn new(value: Value) -> Self {
+            let array = value.as_array().expect("Failed to convert to array");
+            Self {
+                name: array[0].as_str().expect("Failed to convert to str").to_string(),
+                encrypted: array[1].as_str().expect("Failed to convert to str").to_string(),
+                key: array[2].as_str().expect("Failed to convert to str").to_string(),
+                result: array[3].as_str().map(String::from),
+            }
+        }
+    }
+
+    impl Cases {
+        pub fn new() -> Self {
+            let contents = fs::read_to_string("./tests/all_cases.json").expect("Should have been able to read the file");
+
+            serde_json::from_str(&contents).expect("Failed to create cases")
+        }
+    }
+}
@@ -1,18 +1,26 @@
 use std::collections::HashMap;
 
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
 
+use crate::condition::model::Condition;
 use crate::extensions::JsonHelper;
-use crate::model_private::Experiment;
-use crate::model_public::{GrowthBookAttribute, GrowthBookAttributeValue};
+use crate::model_public::{Experiment, GrowthBookAttribute, GrowthBookAttributeValue};
 use crate::range::model::Range;
 
 #[derive(Deserialize, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct GrowthBookResponse {
     pub forced_variations: Option<HashMap<String, i64>>,
+    #[serde(default)]
     pub features: HashMap<String, GrowthBookFeature>,
+    pub encrypted_features: Option<String>,
+    #[serde(default)]
+    pub saved_groups: HashMap<String, Vec<Value>>,
+    pub encrypted_saved_groups: Option<String>,
+    #[serde(default)]
+    pub experiments: Vec<Experiment>,
+    pub encrypted_experiments: Option<String>,
 }
 
 #[derive(Deserialize, Clone, Debug)]
@@ -22,17 +30,46 @@ pub struct GrowthBookFeature {
     pub rules: Option<Vec<GrowthBookFeatureRule>>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
-#[serde(untagged)]
-// needs to be in this order
+#[derive(Clone, Debug)]
 pub enum GrowthBookFeatureRule {
-    Experiment(GrowthBookFeatureRuleExperiment),
+    Experiment(Box<GrowthBookFeatureRuleExperiment>),
     Rollout(GrowthBookFeatureRuleRollout),
     Force(GrowthBookFeatureRuleForce),
     Parent(GrowthBookFeatureRuleParent),
     Empty(Value),
 }
 
+impl<'de> Deserialize<'de> for GrowthBookFeatureRule {
+    // variants are tried in order like an untagged enum, but only when their required keys are present,
+    // so the rule condition is compiled (and an invalid one reported) a single time
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        let has = |key: &str| value.get(key).is_some();
+
+        if has("variations") {
+            if let Ok(rule) = GrowthBookFeatureRuleExperiment::deserialize(&value) {
+                return Ok(GrowthBookFeatureRule::Experiment(Box::new(rule)));
+            }
+        }
+        if has("force") && has("coverage") {
+            if let Ok(rule) = GrowthBookFeatureRuleRollout::deserialize(&value) {
+                return Ok(GrowthBookFeatureRule::Rollout(rule));
+            }
+        }
+        if has("force") {
+            if let Ok(rule) = GrowthBookFeatureRuleForce::deserialize(&value) {
+                return Ok(GrowthBookFeatureRule::Force(rule));
+            }
+        }
+        if has("parentConditions") {
+            if let Ok(rule) = GrowthBookFeatureRuleParent::deserialize(&value) {
+                return Ok(GrowthBookFeatureRule::Parent(rule));
+            }
+        }
+        Ok(GrowthBookFeatureRule::Empty(value))
+    }
+}
+
 #[derive(Deserialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct GrowthBookFeatureRuleForce {
@@ -42,7 +79,7 @@ pub struct GrowthBookFeatureRuleForce {
     pub hash_version: Option<i64>,
     pub filters: Option<Value>,
     pub seed: Option<String>,
-    condition: Option<HashMap<String, Value>>,
+    condition: Option<Condition>,
 }
 
 #[derive(Deserialize, Clone, Debug)]
@@ -51,11 +88,12 @@ pub struct GrowthBookFeatureRuleParent {
     pub parent_conditions: Vec<GrowthBookFeatureRuleParentData>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct GrowthBookFeatureRuleParentData {
     pub id: String,
-    condition: Option<HashMap<String, Value>>,
+    condition: Option<Condition>,
+    #[serde(default)]
     pub gate: bool,
 }
 
@@ -65,7 +103,7 @@ pub struct GrowthBookFeatureRuleRollout {
     pub force: Value,
     pub coverage: f32,
     range: Option<Vec<f32>>,
-    condition: Option<HashMap<String, Value>>,
+    condition: Option<Condition>,
     pub hash_attribute: Option<String>,
     pub fallback_attribute: Option<String>,
     pub hash_version: Option<i64>,
@@ -87,18 +125,32 @@ pub struct GrowthBookFeatureRuleExperiment {
     pub ranges: Option<Vec<Vec<f32>>>,
     pub meta: Option<Value>,
     pub filters: Option<Value>,
-    pub condition: Option<Value>,
+    pub condition: Option<Condition>,
+    pub parent_conditions: Option<Vec<GrowthBookFeatureRuleParentData>>,
+    pub bucket_version: Option<i64>,
+    pub min_bucket_version: Option<i64>,
+    pub disable_sticky_bucketing: Option<bool>,
 }
 
 impl GrowthBookFeatureRuleParentData {
+    pub fn condition(&self) -> Option<&Condition> {
+        self.condition.as_ref()
+    }
+
+    #[deprecated(note = "use `condition` instead")]
     pub fn conditions(&self) -> Option<Vec<GrowthBookAttribute>> {
-        option_map_to_attributes(self.condition.clone())
+        condition_attributes(&self.condition)
     }
 }
 
 impl GrowthBookFeatureRuleRollout {
+    pub fn condition(&self) -> Option<&Condition> {
+        self.condition.as_ref()
+    }
+
+    #[deprecated(note = "use `condition` instead")]
     pub fn conditions(&self) -> Option<Vec<GrowthBookAttribute>> {
-        option_map_to_attributes(self.condition.clone())
+        condition_attributes(&self.condition)
     }
 
     pub fn range(&self) -> Option<Range> {
@@ -111,8 +163,13 @@ impl GrowthBookFeatureRuleRollout {
 }
 
 impl GrowthBookFeatureRuleForce {
+    pub fn condition(&self) -> Option<&Condition> {
+        self.condition.as_ref()
+    }
+
+    #[deprecated(note = "use `condition` instead")]
     pub fn conditions(&self) -> Option<Vec<GrowthBookAttribute>> {
-        option_map_to_attributes(self.condition.clone())
+        condition_attributes(&self.condition)
     }
 
     pub fn range(&self) -> Option<Range> {
@@ -125,6 +182,7 @@ impl GrowthBookFeatureRuleForce {
 }
 
 impl GrowthBookFeatureRuleExperiment {
+    #[deprecated(note = "experiment rules are evaluated through `Experiment::run`")]
     pub fn seed(
         &self,
         feature_name: &str,
@@ -132,6 +190,7 @@ impl GrowthBookFeatureRuleExperiment {
         self.seed.clone().unwrap_or(self.key.clone().unwrap_or(feature_name.to_string()))
     }
 
+    #[deprecated(note = "experiment rules are evaluated through `Experiment::run`")]
     pub fn ranges(&self) -> Vec<Range> {
         if let Some(ranges) = self.ranges.clone() {
             ranges.iter().map(|range| Range { start: range[0], end: range[1] }).collect()
@@ -140,6 +199,7 @@ impl GrowthBookFeatureRuleExperiment {
         }
     }
 
+    #[deprecated(note = "experiment rules are evaluated through `Experiment::run`")]
     pub fn namespace_range(&self) -> Option<(String, Range)> {
         self.namespace.as_ref().map(|namespace| {
             (
@@ -152,8 +212,12 @@ impl GrowthBookFeatureRuleExperiment {
         })
     }
 
-    pub fn model_experiment(&self) -> Experiment {
+    pub fn model_experiment(
+        &self,
+        feature_name: &str,
+    ) -> Experiment {
         Experiment {
+            key: self.key.clone().unwrap_or(feature_name.to_string()),
             name: self.name.clone(),
             seed: self.seed.clone(),
             hash_version: self.hash_version,
@@ -166,10 +230,23 @@ impl GrowthBookFeatureRuleExperiment {
             variations: self.variations.clone(),
             weights: self.weights.clone(),
             condition: self.condition.clone(),
+            bucket_version: self.bucket_version,
+            min_bucket_version: self.min_bucket_version,
+            fallback_attribute: self.fallback_attribute.clone(),
+            disable_sticky_bucketing: self.disable_sticky_bucketing,
+            ..Experiment::default()
         }
     }
 }
 
+#[deprecated(note = "conditions are compiled when the payload is loaded, use `Condition` instead")]
 pub fn option_map_to_attributes(option_map: Option<HashMap<String, Value>>) -> Option<Vec<GrowthBookAttribute>> {
     option_map.map(|conditions| conditions.iter().map(|(k, v)| GrowthBookAttribute::new(k.clone(), GrowthBookAttributeValue::from(v.clone()))).collect())
 }
+
+fn condition_attributes(condition: &Option<Condition>) -> Option<Vec<GrowthBookAttribute>> {
+    condition
+        .as_ref()
+        .and_then(|it| it.source.as_object())
+        .map(|conditions| conditions.iter().map(|(k, v)| GrowthBookAttribute::new(k.clone(), GrowthBookAttributeValue::from(v.clone()))).collect())
+}
@@ -1,5 +1,7 @@
 use std::env;
 
+use crate::error::{GrowthbookError, GrowthbookErrorCode};
+
 pub struct Environment;
 
 impl Environment {
@@ -16,4 +18,37 @@ impl Environment {
     ) -> u64 {
         env::var(env_name).ok().map(|env| env.parse::<u64>().unwrap_or(default)).unwrap_or(default)
     }
+
+    pub fn required_string(env_name: &str) -> Result<String, GrowthbookError> {
+        Self::optional_string(env_name).ok_or_else(|| GrowthbookError::new(GrowthbookErrorCode::MissingEnvironmentVariable, &format!("Environment variable {env_name} is not set")))
+    }
+
+    pub fn optional_string(env_name: &str) -> Option<String> {
+        env::var(env_name).ok().filter(|env| !env.trim().is_empty())
+    }
+
+    pub fn optional_u64(env_name: &str) -> Result<Option<u64>, GrowthbookError> {
+        Self::optional_string(env_name)
+            .map(|env| {
+                env.trim()
+                    .parse::<u64>()
+                    .map_err(|e| GrowthbookError::new(GrowthbookErrorCode::ParseError, &format!("Environment variable {env_name} is not a number: {e}")))
+            })
+            .transpose()
+    }
+
+    pub fn bool_or_default(
+        env_name: &str,
+        default: bool,
+    ) -> Result<bool, GrowthbookError> {
+        match Self::optional_string(env_name).map(|env| env.trim().to_lowercase()) {
+            None => Ok(default),
+            Some(env) if env == "true" || env == "1" => Ok(true),
+            Some(env) if env == "false" || env == "0" => Ok(false),
+            Some(env) => Err(GrowthbookError::new(
+                GrowthbookErrorCode::ParseError,
+                &format!("Environment variable {env_name} is not a boolean: {env}"),
+            )),
+        }
+    }
 }
@@ -1,10 +1,14 @@
 use std::env::VarError;
 use std::error::Error;
 use std::fmt::{Display, Formatter};
+use std::io;
 use std::num::ParseIntError;
+use std::path::PathBuf;
+use std::time::Duration;
 
-use chrono::OutOfRangeError;
-use reqwest::Response;
+use chrono::{DateTime, OutOfRangeError, Utc};
+use reqwest::header::RETRY_AFTER;
+use reqwest::{Response, StatusCode};
 
 #[derive(Debug)]
 pub enum GrowthbookErrorCode {
@@ -15,8 +19,33 @@ pub enum GrowthbookErrorCode {
     MissingEnvironmentVariable,
     GrowthbookGateway,
     GrowthbookGatewayDeserialize,
+    GrowthbookGatewayInvalidSdkKey,
+    GrowthbookGatewayNotFound,
+    GrowthbookGatewayRateLimited { retry_after: Option<Duration> },
+    GrowthbookGatewayServerError,
+    GrowthbookGatewayTimeout,
     InvalidResponseValueType,
     GrowthBookAttributeIsNotObject,
+    DecryptionError,
+    GrowthbookClientNotReady,
+    MissingTokioRuntime,
+    FeaturesCacheError,
+    IoError,
+    InvalidConfiguration(ConfigurationError),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ConfigurationError {
+    MissingApiHost,
+    InvalidApiHost(String),
+    MissingClientKey,
+    InvalidDecryptionKey,
+    InvalidUpdateInterval,
+    InvalidHttpTimeout,
+    InvalidHeader(String),
+    InvalidBootstrapPayload(String),
+    InvalidCachePath(PathBuf),
+    InvalidForcedVariation(String),
 }
 
 #[derive(Debug)]
@@ -34,6 +63,27 @@ impl GrowthbookError {
     }
 }
 
+impl From<ConfigurationError> for GrowthbookError {
+    fn from(error: ConfigurationError) -> Self {
+        let message = match &error {
+            ConfigurationError::MissingApiHost => String::from("API host is required"),
+            ConfigurationError::InvalidApiHost(host) => format!("API host '{host}' must be an absolute http(s) url"),
+            ConfigurationError::MissingClientKey => String::from("Client key is required"),
+            ConfigurationError::InvalidDecryptionKey => String::from("Decryption key must be a base64 encoded 128 or 256 bits key"),
+            ConfigurationError::InvalidUpdateInterval => String::from("Update interval must be greater than zero"),
+            ConfigurationError::InvalidHttpTimeout => String::from("HTTP timeout must be greater than zero"),
+            ConfigurationError::InvalidHeader(name) => format!("Header '{name}' has an invalid name or value"),
+            ConfigurationError::InvalidBootstrapPayload(reason) => format!("Bootstrap payload is invalid: {reason}"),
+            ConfigurationError::InvalidCachePath(path) => format!("Cache path {path:?} is not inside an existing directory"),
+            ConfigurationError::InvalidForcedVariation(key) => format!("Forced variation for '{key}' must not be negative"),
+        };
+        Self {
+            code: GrowthbookErrorCode::InvalidConfiguration(error),
+            message,
+        }
+    }
+}
+
 impl Display for GrowthbookError {
     fn fmt(
         &self,
@@ -60,19 +110,22 @@ impl From<Box<dyn Error>> for GrowthbookError {
 
 impl From<reqwest_middleware::Error> for GrowthbookError {
     fn from(error: reqwest_middleware::Error) -> Self {
-        Self {
-            code: GrowthbookErrorCode::GrowthbookGateway,
-            message: error.to_string(),
-        }
+        let code = match &error {
+            reqwest_middleware::Error::Reqwest(it) if it.is_timeout() => GrowthbookErrorCode::GrowthbookGatewayTimeout,
+            _ => GrowthbookErrorCode::GrowthbookGateway,
+        };
+        Self { code, message: error.to_string() }
     }
 }
 
 impl From<reqwest::Error> for GrowthbookError {
     fn from(error: reqwest::Error) -> Self {
-        Self {
-            code: GrowthbookErrorCode::GrowthbookGatewayDeserialize,
-            message: error.to_string(),
-        }
+        let code = if error.is_timeout() {
+            GrowthbookErrorCode::GrowthbookGatewayTimeout
+        } else {
+            GrowthbookErrorCode::GrowthbookGatewayDeserialize
+        };
+        Self { code, message: error.to_string() }
     }
 }
 
@@ -103,6 +156,15 @@ impl From<serde_json::Error> for GrowthbookError {
     }
 }
 
+impl From<io::Error> for GrowthbookError {
+    fn from(error: io::Error) -> Self {
+        Self {
+            code: GrowthbookErrorCode::IoError,
+            message: error.to_string(),
+        }
+    }
+}
+
 impl From<OutOfRangeError> for GrowthbookError {
     fn from(error: OutOfRangeError) -> Self {
         Self {
@@ -114,9 +176,28 @@ impl From<OutOfRangeError> for GrowthbookError {
 
 impl From<Response> for GrowthbookError {
     fn from(response: Response) -> Self {
+        let status = response.status();
+        let (code, reason) = match status {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => (GrowthbookErrorCode::GrowthbookGatewayInvalidSdkKey, "Invalid SDK key"),
+            StatusCode::NOT_FOUND => (GrowthbookErrorCode::GrowthbookGatewayNotFound, "SDK key or endpoint not found"),
+            StatusCode::TOO_MANY_REQUESTS => (GrowthbookErrorCode::GrowthbookGatewayRateLimited { retry_after: retry_after(&response) }, "Rate limited"),
+            StatusCode::REQUEST_TIMEOUT | StatusCode::GATEWAY_TIMEOUT => (GrowthbookErrorCode::GrowthbookGatewayTimeout, "Timed out"),
+            it if it.is_server_error() => (GrowthbookErrorCode::GrowthbookGatewayServerError, "Server error"),
+            _ => (GrowthbookErrorCode::GrowthbookGateway, "Failed to get features"),
+        };
         Self {
-            code: GrowthbookErrorCode::GrowthbookGateway,
-            message: format!("Failed to get features. StatusCode={}", response.status()),
+            code,
+            message: format!("{reason}. StatusCode={status}"),
         }
     }
 }
+
+// retry-after is either delay seconds or an http date
+fn retry_after(response: &Response) -> Option<Duration> {
+    let value = response.headers().get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    (date.with_timezone(&Utc) - Utc::now()).to_std().ok()
+}
@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+
+pub struct ForcedVariations<'a> {
+    forced_variations: &'a Option<HashMap<String, i64>>,
+    overrides: Option<&'a HashMap<String, i64>>,
+    url: Option<&'a str>,
+}
+
+impl<'a> ForcedVariations<'a> {
+    pub fn new(
+        forced_variations: &'a Option<HashMap<String, i64>>,
+        url: Option<&'a str>,
+    ) -> Self {
+        ForcedVariations {
+            forced_variations,
+            overrides: None,
+            url,
+        }
+    }
+
+    pub fn with_overrides(
+        self,
+        overrides: &'a HashMap<String, i64>,
+    ) -> Self {
+        ForcedVariations { overrides: Some(overrides), ..self }
+    }
+
+    pub fn get(
+        &self,
+        experiment_key: &str,
+        num_variations: usize,
+    ) -> Option<i64> {
+        self.url
+            .and_then(|url| query_string_override(experiment_key, url, num_variations))
+            .or_else(|| self.overrides.and_then(|it| it.get(experiment_key)).copied())
+            .or_else(|| self.forced_variations.as_ref().and_then(|it| it.get(experiment_key)).copied())
+    }
+}
+
+pub fn query_string_override(
+    experiment_key: &str,
+    url: &str,
+    num_variations: usize,
+) -> Option<i64> {
+    let (_, search) = url.split_once('?')?;
+    let search = search.split('#').next().unwrap_or_default();
+    let search = search.split('?').next().unwrap_or_default();
+
+    let value = search.split('&').find_map(|pair| {
+        let mut key_value = pair.splitn(2, '=');
+        if key_value.next() == Some(experiment_key) {
+            Some(key_value.next().unwrap_or_default())
+        } else {
+            None
+        }
+    })?;
+
+    let variation = parse_int(value)?;
+    if variation >= 0 && (variation as usize) < num_variations {
+        Some(variation)
+    } else {
+        None
+    }
+}
+
+// mirrors javascript parseInt: optional sign followed by the leading digits
+fn parse_int(value: &str) -> Option<i64> {
+    let value = value.trim_start();
+    let (sign, digits) = match value.strip_prefix('-') {
+        Some(rest) => (-1, rest),
+        None => (1, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let end = digits.find(|it: char| !it.is_ascii_digit()).unwrap_or(digits.len());
+    digits[..end].parse::<i64>().ok().map(|it| sign * it)
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use serde::Deserialize;
+    use serde_json::Value;
+
+    use crate::experiment::forced_variation::query_string_override;
+
+    #[tokio::test]
+    async fn evaluate_query_string_override() -> Result<(), Box<dyn std::error::Error>> {
+        let cases = Cases::new();
+
+        for value in cases.get_query_string_override {
+            let eval_override = EvalQueryStringOverride::new(value);
+            let result = query_string_override(&eval_override.experiment_key, &eval_override.url, eval_override.num_variations);
+            assert_eq!(eval_override.expected, result, "Invalid override for '{}'", eval_override.name);
+        }
+
+        Ok(())
+    }
+
+    #[derive(Deserialize, Clone)]
+    #[serde(rename_all = "camelCase")]
+    struct Cases {
+        get_query_string_override: Vec<Value>,
+    }
+
+    pub struct EvalQueryStringOverride {
+        name: String,
+        experiment_key: String,
+        url: String,
+        num_variations: usize,
+        expected: Option<i64>,
+    }
+
+    impl EvalQueryStringOverride {
+        fn new(value: Value) -> Self {
+            let array = value.as_array().expect("Failed to convert to array");
+            Self {
+                name: array[0].as_str().expect("Failed to convert to str").to_string(),
+                experiment_key: array[1].as_str().expect("Failed to convert to str").to_string(),
+                url: array[2].as_str().expect("Failed to convert to str").to_string(),
+                num_variations: array[3].as_u64().expect("Failed to convert to u64") as usize,
+                expected: array[4].as_i64(),
+            }
+        }
+    }
+
+    impl Cases {
+        pub fn new() -> Self {
+            let contents = fs::read_to_string("./tests/all_cases.json").expect("Should have been able to read the file");
+
+            serde_json::from_str(&contents).expect("Failed to create cases")
+        }
+    }
+}
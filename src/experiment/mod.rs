@@ -0,0 +1,2 @@
+pub mod forced_variation;
+pub mod use_case;
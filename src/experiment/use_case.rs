@@ -0,0 +1,340 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::condition::saved_group::SavedGroups;
+use crate::dto::GrowthBookFeature;
+use crate::experiment::forced_variation::ForcedVariations;
+use crate::extensions::{FindGrowthBookAttribute, JsonHelper};
+use crate::feature::feature_rule_experiment::choose_variation;
+use crate::feature::feature_rule_parent::{check_parent_conditions, ParentConditionsOutcome};
+use crate::filter::use_case::Filter;
+use crate::hash::{HashCode, HashCodeVersion};
+use crate::model_public::{Experiment, ExperimentResult, GrowthBookAttribute, GrowthBookAttributeValue};
+use crate::namespace::use_case::Namespace;
+use crate::range::model::Range;
+use crate::sticky_bucket::service::StickyBucketService;
+use crate::sticky_bucket::use_case::StickyBucket;
+use crate::tracking::ExperimentTracker;
+use crate::url_redirect::use_case::UrlTargeting;
+
+impl Experiment {
+    #[allow(clippy::too_many_arguments)]
+    pub fn run(
+        &self,
+        feature_id: Option<&str>,
+        url: Option<&str>,
+        user_attributes: &Vec<GrowthBookAttribute>,
+        forced_variations: &ForcedVariations,
+        sticky_bucket_service: &Option<Arc<dyn StickyBucketService>>,
+        experiment_tracker: &Option<Arc<ExperimentTracker>>,
+        saved_groups: &SavedGroups,
+        all_features: &HashMap<String, GrowthBookFeature>,
+    ) -> ExperimentResult {
+        let sticky_bucket_service = sticky_bucket_service.as_ref().filter(|_| !self.disable_sticky_bucketing.unwrap_or(false));
+        let hash = self.get_hash_attribute_value(user_attributes, sticky_bucket_service.is_some());
+
+        if self.variations.len() < 2 {
+            return self.result(feature_id, -1, false, &hash, None, false);
+        }
+
+        if let Some(forced_variation) = forced_variations.get(&self.key, self.variations.len()) {
+            return self.result(feature_id, forced_variation, false, &hash, None, false);
+        }
+
+        if self.active == Some(false) {
+            return self.result(feature_id, -1, false, &hash, None, false);
+        }
+
+        let Some((hash_attribute, user_value)) = hash.clone() else {
+            return self.result(feature_id, -1, false, &hash, None, false);
+        };
+
+        let sticky_bucket = sticky_bucket_service.map(|service| {
+            StickyBucket::get_variation(
+                service.as_ref(),
+                &self.key,
+                self.bucket_version,
+                self.min_bucket_version,
+                &self.meta,
+                &self.hash_attribute.clone().unwrap_or(String::from("id")),
+                self.fallback_attribute.as_deref(),
+                user_attributes,
+            )
+        });
+        let sticky_variation = sticky_bucket.as_ref().and_then(|it| it.variation);
+
+        if sticky_variation.is_none()
+            && !self.is_eligible(
+                feature_id,
+                url,
+                &hash_attribute,
+                &user_value,
+                user_attributes,
+                forced_variations,
+                sticky_bucket_service,
+                experiment_tracker,
+                saved_groups,
+                all_features,
+            )
+        {
+            return self.result(feature_id, -1, false, &hash, None, false);
+        }
+
+        let seed = self.seed.clone().unwrap_or(self.key.clone());
+        let Some(user_weight) = HashCode::hash_code(&user_value.to_string(), &seed, HashCodeVersion::from(self.hash_version)) else {
+            return self.result(feature_id, -1, false, &hash, None, false);
+        };
+
+        let index = match sticky_variation {
+            Some(variation) => variation as i64,
+            None => choose_variation(user_weight, self.ranges()),
+        };
+
+        if sticky_bucket.as_ref().map(|it| it.version_is_blocked).unwrap_or(false) {
+            return self.result(feature_id, -1, false, &hash, None, true);
+        }
+
+        if index < 0 {
+            return self.result(feature_id, -1, false, &hash, None, false);
+        }
+
+        if let Some(force) = self.force {
+            return self.result(feature_id, force, false, &hash, None, false);
+        }
+
+        let result = self.result(feature_id, index, true, &hash, Some(user_weight), sticky_variation.is_some());
+
+        if let Some(service) = sticky_bucket_service {
+            StickyBucket::save_assignment(service.as_ref(), &hash_attribute, &user_value.to_string(), &self.key, self.bucket_version, &result.key);
+        }
+
+        result
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn is_eligible(
+        &self,
+        feature_id: Option<&str>,
+        url: Option<&str>,
+        hash_attribute: &str,
+        user_value: &GrowthBookAttributeValue,
+        user_attributes: &Vec<GrowthBookAttribute>,
+        forced_variations: &ForcedVariations,
+        sticky_bucket_service: Option<&Arc<dyn StickyBucketService>>,
+        experiment_tracker: &Option<Arc<ExperimentTracker>>,
+        saved_groups: &SavedGroups,
+        all_features: &HashMap<String, GrowthBookFeature>,
+    ) -> bool {
+        if let Some(filters) = &self.filters {
+            if Filter::is_filtered_out(filters, hash_attribute, user_attributes) {
+                return false;
+            }
+        } else if let Some(namespace) = &self.namespace {
+            let range = Range {
+                start: namespace[1].force_f32(0.0),
+                end: namespace[2].force_f32(1.0),
+            };
+            if !Namespace::is_in(user_value, &namespace[0].force_string(""), &range) {
+                return false;
+            }
+        }
+
+        if let Some(condition) = &self.condition {
+            if !condition.matches(user_attributes, saved_groups) {
+                return false;
+            }
+        }
+
+        // without a url there is nothing to target, so url targeted experiments exclude everyone
+        if let Some(url_patterns) = &self.url_patterns {
+            if !url.is_some_and(|url| UrlTargeting::is_targeted(url, url_patterns)) {
+                return false;
+            }
+        }
+
+        if let Some(parent_conditions) = &self.parent_conditions {
+            let feature_name = feature_id.unwrap_or(&self.key);
+            let outcome = check_parent_conditions(
+                parent_conditions,
+                feature_name,
+                &[],
+                user_attributes,
+                forced_variations,
+                &sticky_bucket_service.cloned(),
+                experiment_tracker,
+                saved_groups,
+                all_features,
+            );
+            if !matches!(outcome, ParentConditionsOutcome::Met) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    fn get_hash_attribute_value(
+        &self,
+        user_attributes: &Vec<GrowthBookAttribute>,
+        sticky_bucketing: bool,
+    ) -> Option<(String, GrowthBookAttributeValue)> {
+        let hash_attribute = self.hash_attribute.clone().unwrap_or(String::from("id"));
+        // the fallback attribute only makes sense when the assignment can be kept in a sticky bucket
+        let fallback_attribute = self.fallback_attribute.clone().filter(|_| sticky_bucketing);
+        let attributes = std::iter::once(hash_attribute).chain(fallback_attribute);
+        for attribute in attributes {
+            match user_attributes.find_value(&attribute) {
+                None | Some(GrowthBookAttributeValue::Empty) | Some(GrowthBookAttributeValue::Null) => continue,
+                Some(GrowthBookAttributeValue::String(it)) if it.is_empty() => continue,
+                Some(user_value) => return Some((attribute, user_value)),
+            }
+        }
+        None
+    }
+
+    fn ranges(&self) -> Vec<Range> {
+        if let Some(ranges) = &self.ranges {
+            ranges.iter().map(|range| Range { start: range[0], end: range[1] }).collect()
+        } else {
+            Range::get_bucket_range(self.variations.len() as i64, &self.coverage, self.weights.clone())
+        }
+    }
+
+    fn result(
+        &self,
+        feature_id: Option<&str>,
+        variation_index: i64,
+        hash_used: bool,
+        hash: &Option<(String, GrowthBookAttributeValue)>,
+        bucket: Option<f32>,
+        sticky_bucket_used: bool,
+    ) -> ExperimentResult {
+        let in_experiment = variation_index >= 0 && (variation_index as usize) < self.variations.len();
+        let variation_index = if in_experiment { variation_index } else { 0 };
+        let key = self
+            .meta
+            .as_ref()
+            .and_then(|meta| meta.force_array(vec![]).get(variation_index as usize).and_then(|it| it.get("key")).map(|it| it.force_string("")))
+            .unwrap_or(format!("{variation_index}"));
+
+        ExperimentResult {
+            feature_id: feature_id.map(String::from).unwrap_or_default(),
+            value: self.variations.get(variation_index as usize).cloned().unwrap_or_default(),
+            variation_id: variation_index,
+            in_experiment,
+            hash_used,
+            hash_attribute: Some(
+                hash.as_ref()
+                    .map(|(attribute, _)| attribute.clone())
+                    .unwrap_or(self.hash_attribute.clone().unwrap_or(String::from("id"))),
+            ),
+            hash_value: hash.as_ref().map(|(_, value)| value.to_value()),
+            bucket,
+            key,
+            sticky_bucket_used,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+    use std::fs;
+
+    use serde::Deserialize;
+    use serde_json::Value;
+
+    use crate::condition::saved_group::SavedGroups;
+    use crate::dto::GrowthBookFeature;
+    use crate::experiment::forced_variation::ForcedVariations;
+    use crate::model_public::{Experiment, GrowthBookAttribute};
+
+    #[tokio::test]
+    async fn evaluate_run() -> Result<(), Box<dyn std::error::Error>> {
+        let cases = Cases::new();
+
+        for value in cases.run {
+            let eval_run = EvalRun::new(value);
+            if eval_run.unsupported_context {
+                continue;
+            }
+
+            let result = eval_run.experiment.run(
+                None,
+                eval_run.url.as_deref(),
+                &eval_run.attributes,
+                &ForcedVariations::new(&eval_run.forced_variations, eval_run.url.as_deref()),
+                &None,
+                &None,
+                &SavedGroups::default(),
+                &eval_run.features,
+            );
+
+            assert_eq!(eval_run.value, result.value, "Invalid value for '{}'", eval_run.name);
+            assert_eq!(eval_run.in_experiment, result.in_experiment, "Invalid in_experiment for '{}'", eval_run.name);
+            assert_eq!(eval_run.hash_used, result.hash_used, "Invalid hash_used for '{}'", eval_run.name);
+        }
+
+        Ok(())
+    }
+
+    #[derive(Deserialize, Clone)]
+    #[serde(rename_all = "camelCase")]
+    struct Cases {
+        run: Vec<Value>,
+    }
+
+    #[derive(Deserialize, Clone)]
+    #[serde(rename_all = "camelCase")]
+    struct RunContext {
+        attributes: Option<Value>,
+        forced_variations: Option<HashMap<String, i64>>,
+        features: Option<HashMap<String, GrowthBookFeature>>,
+        url: Option<String>,
+    }
+
+    pub struct EvalRun {
+        name: String,
+        attributes: Vec<GrowthBookAttribute>,
+        forced_variations: Option<HashMap<String, i64>>,
+        features: HashMap<String, GrowthBookFeature>,
+        url: Option<String>,
+        unsupported_context: bool,
+        experiment: Experiment,
+        value: Value,
+        in_experiment: bool,
+        hash_used: bool,
+    }
+
+    impl EvalRun {
+        fn new(value: Value) -> Self {
+            let array = value.as_array().expect("Failed to convert to array");
+            let context = serde_json::from_value::<RunContext>(array[1].clone()).expect("Failed to convert to RunContext");
+            // qa mode and a disabled context are not supported by the client yet
+            let unsupported_context = ["qaMode", "enabled"].iter().any(|key| array[1].get(key).is_some());
+            Self {
+                name: array[0].as_str().expect("Failed to convert to str").to_string(),
+                attributes: context
+                    .attributes
+                    .map(|attributes| GrowthBookAttribute::from(attributes).expect("Failed to convert to GrowthBookAttribute"))
+                    .unwrap_or_default(),
+                forced_variations: context.forced_variations,
+                features: context.features.unwrap_or_default(),
+                url: context.url,
+                unsupported_context,
+                experiment: serde_json::from_value(array[2].clone()).expect("Failed to convert to Experiment"),
+                value: array[3].clone(),
+                in_experiment: array[4].as_bool().expect("Failed to convert to bool"),
+                hash_used: array[5].as_bool().expect("Failed to convert to bool"),
+            }
+        }
+    }
+
+    impl Cases {
+        pub fn new() -> Self {
+            let contents = fs::read_to_string("./tests/all_cases.json").expect("Should have been able to read the file");
+
+            serde_json::from_str(&contents).expect("Failed to create cases")
+        }
+    }
+}
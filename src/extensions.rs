@@ -143,7 +143,7 @@ fn look_for_attribute(
         if split.len().gt(&(split_index + 1)) {
             match found_attribute.value.clone() {
                 GrowthBookAttributeValue::Object(it) => look_for_attribute(split_index + 1, attribute_key, &it),
-                GrowthBookAttributeValue::Empty => None,
+                GrowthBookAttributeValue::Empty | GrowthBookAttributeValue::Null => None,
                 _ => Some(found_attribute.clone()),
             }
         } else {
@@ -1,169 +1,78 @@
 use std::collections::HashMap;
-
-use serde_json::Value;
-
-use crate::dto::GrowthBookFeatureRuleExperiment;
-use crate::extensions::{FindGrowthBookAttribute, JsonHelper};
-use crate::hash::{HashCode, HashCodeVersion};
-use crate::model_private::{ExperimentResult, FeatureResult};
+use std::sync::Arc;
+
+use crate::condition::saved_group::SavedGroups;
+use crate::dto::{GrowthBookFeature, GrowthBookFeatureRuleExperiment};
+use crate::experiment::forced_variation::ForcedVariations;
+use crate::extensions::JsonHelper;
+use crate::feature::feature_rule_parent::{check_parent_conditions, ParentConditionsOutcome};
+use crate::model_private::FeatureResult;
 use crate::model_public::GrowthBookAttribute;
-use crate::namespace::use_case::Namespace;
 use crate::range::model::Range;
+use crate::sticky_bucket::service::StickyBucketService;
+use crate::tracking::ExperimentTracker;
 
 impl GrowthBookFeatureRuleExperiment {
+    #[allow(clippy::too_many_arguments)]
     pub fn get_match_value(
         &self,
         feature_name: &str,
+        feature_name_decorate: &[String],
         user_attributes: &Vec<GrowthBookAttribute>,
-        forced_variations: &Option<HashMap<String, i64>>,
-    ) -> Option<FeatureResult> {
-        if let Some(feature_attribute) = &self.hash_attribute {
-            self.check_experiment(&feature_name, user_attributes, forced_variations, feature_attribute)
-        } else {
-            let fallback_attribute = self.get_fallback_attribute();
-            self.check_experiment(&feature_name, user_attributes, forced_variations, &fallback_attribute)
-        }
-    }
-
-    fn check_experiment(
-        &self,
-        feature_name: &&str,
-        user_attributes: &Vec<GrowthBookAttribute>,
-        forced_variations: &Option<HashMap<String, i64>>,
-        feature_attribute: &str,
+        forced_variations: &ForcedVariations,
+        sticky_bucket_service: &Option<Arc<dyn StickyBucketService>>,
+        experiment_tracker: &Option<Arc<ExperimentTracker>>,
+        saved_groups: &SavedGroups,
+        all_features: &HashMap<String, GrowthBookFeature>,
     ) -> Option<FeatureResult> {
-        if let Some(user_value) = user_attributes.find_value(feature_attribute) {
-            if let Some((namespace, range)) = &self.namespace_range() {
-                if !Namespace::is_in(&user_value, namespace, range) {
-                    return None;
-                }
-            }
-
-            if let Some(forced_variation) = self.forced_variation(feature_name, user_attributes, forced_variations) {
-                return Some(forced_variation);
-            }
-
-            let user_weight = HashCode::hash_code(&user_value.to_string(), &self.seed(feature_name), HashCodeVersion::from(self.hash_version)).unwrap_or(-1.0);
-            let ranges = self.ranges();
-            let index = choose_variation(user_weight, ranges);
-            if index >= 0 {
-                let usize_index = index as usize;
-                let value = self.variations[usize_index].clone();
-                let (meta_value, pass_through) = self.get_meta_value(usize_index);
-                if !pass_through {
-                    return Some(FeatureResult::experiment(
-                        value.clone(),
-                        self.model_experiment(),
-                        create_experiment_result(
-                            feature_name,
-                            value.clone(),
-                            index,
-                            true,
-                            Some(feature_attribute.to_string()),
-                            Some(user_value.to_value()),
-                            Some(user_weight),
-                            meta_value,
-                        ),
-                    ));
-                }
+        if let Some(parent_conditions) = &self.parent_conditions {
+            match check_parent_conditions(
+                parent_conditions,
+                feature_name,
+                feature_name_decorate,
+                user_attributes,
+                forced_variations,
+                sticky_bucket_service,
+                experiment_tracker,
+                saved_groups,
+                all_features,
+            ) {
+                ParentConditionsOutcome::Met => {},
+                ParentConditionsOutcome::NotMet => return None,
+                ParentConditionsOutcome::Blocked(feature) => return Some(*feature),
             }
         }
 
-        None
-    }
-
-    fn forced_variation(
-        &self,
-        feature_name: &str,
-        user_attributes: &Vec<GrowthBookAttribute>,
-        forced_variations: &Option<HashMap<String, i64>>,
-    ) -> Option<FeatureResult> {
-        if let Some(forced_variations) = forced_variations {
-            if let Some(found_forced_variation) = forced_variations.get(feature_name) {
-                let hash_attribute = self.hash_attribute.clone().unwrap_or(self.get_fallback_attribute());
-                if let Some(user_value) = user_attributes.find_value(&hash_attribute) {
-                    let forced_variation_index = *found_forced_variation as usize;
-                    let value = self.variations[forced_variation_index].clone();
-                    let (meta_value, pass_through) = self.get_meta_value(forced_variation_index);
-                    if !pass_through {
-                        return Some(FeatureResult::experiment(
-                            value.clone(),
-                            self.model_experiment(),
-                            create_experiment_result(
-                                feature_name,
-                                value.clone(),
-                                *found_forced_variation,
-                                true,
-                                self.hash_attribute.clone(),
-                                Some(user_value.to_value()),
-                                None,
-                                meta_value,
-                            ),
-                        ));
-                    }
-                }
-            }
+        let experiment = self.model_experiment(feature_name);
+        let experiment_result = experiment.run(
+            Some(feature_name),
+            None,
+            user_attributes,
+            forced_variations,
+            sticky_bucket_service,
+            experiment_tracker,
+            saved_groups,
+            all_features,
+        );
+        if experiment_result.in_experiment && !self.is_pass_through(experiment_result.variation_id as usize) {
+            Some(FeatureResult::experiment(experiment_result.value.clone(), experiment, experiment_result))
+        } else {
+            None
         }
-        None
     }
 
-    fn get_meta_value(
+    fn is_pass_through(
         &self,
-        usize_index: usize,
-    ) -> (String, bool) {
-        match &self.meta {
-            None => (format!("{usize_index}"), false),
-            Some(it) => {
-                if let Some(meta_value) = it.force_array(vec![]).get(usize_index) {
-                    let pass_through = if let Some(pass_through_value) = meta_value.get("passthrough") {
-                        pass_through_value.force_bool(false)
-                    } else {
-                        false
-                    };
-
-                    if let Some(key) = meta_value.get("key") {
-                        (key.force_string(""), pass_through)
-                    } else {
-                        (format!("{usize_index}"), pass_through)
-                    }
-                } else {
-                    (format!("{usize_index}"), false)
-                }
-            },
-        }
-    }
-
-    fn get_fallback_attribute(&self) -> String {
-        self.fallback_attribute.clone().unwrap_or(String::from("id"))
-    }
-}
-
-#[allow(clippy::too_many_arguments)]
-fn create_experiment_result(
-    feature_name: &str,
-    value: Value,
-    variation_id: i64,
-    hash_used: bool,
-    hash_attribute: Option<String>,
-    hash_value: Option<Value>,
-    bucket: Option<f32>,
-    key: String,
-) -> ExperimentResult {
-    ExperimentResult {
-        feature_id: String::from(feature_name),
-        value,
-        variation_id,
-        in_experiment: true,
-        hash_used,
-        hash_attribute,
-        hash_value,
-        bucket,
-        key,
-        sticky_bucket_used: false,
+        variation_index: usize,
+    ) -> bool {
+        self.meta
+            .as_ref()
+            .and_then(|meta| meta.force_array(vec![]).get(variation_index).and_then(|it| it.get("passthrough")).map(|it| it.force_bool(false)))
+            .unwrap_or(false)
     }
 }
 
-fn choose_variation(
+pub fn choose_variation(
     user_weight: f32,
     ranges: Vec<Range>,
 ) -> i64 {
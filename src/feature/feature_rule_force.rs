@@ -1,4 +1,4 @@
-use crate::condition::use_case::ConditionsMatchesAttributes;
+use crate::condition::saved_group::SavedGroups;
 use crate::coverage::model::Coverage;
 use crate::dto::GrowthBookFeatureRuleForce;
 use crate::extensions::FindGrowthBookAttribute;
@@ -11,6 +11,7 @@ impl GrowthBookFeatureRuleForce {
         &self,
         feature_name: &str,
         user_attributes: &Vec<GrowthBookAttribute>,
+        saved_groups: &SavedGroups,
     ) -> Option<FeatureResult> {
         if let Some(filters) = &self.filters {
             let hash_attribute = self.get_fallback_attribute();
@@ -19,8 +20,8 @@ impl GrowthBookFeatureRuleForce {
             }
         }
 
-        if let Some(feature_attributes) = self.conditions() {
-            if feature_attributes.matches(user_attributes) {
+        if let Some(condition) = self.condition() {
+            if condition.matches(user_attributes, saved_groups) {
                 self.check_range_or_force(feature_name, user_attributes)
             } else {
                 None
@@ -1,17 +1,85 @@
-use crate::condition::use_case::ConditionsMatchesAttributes;
-use crate::dto::GrowthBookFeatureRuleParentData;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::condition::saved_group::SavedGroups;
+use crate::dto::{GrowthBookFeature, GrowthBookFeatureRuleParentData};
+use crate::experiment::forced_variation::ForcedVariations;
 use crate::model_private::FeatureResult;
 use crate::model_public::{GrowthBookAttribute, GrowthBookAttributeValue};
+use crate::sticky_bucket::service::StickyBucketService;
+use crate::tracking::ExperimentTracker;
 
 impl GrowthBookFeatureRuleParentData {
     pub fn is_met(
         &self,
         feature: FeatureResult,
+        saved_groups: &SavedGroups,
     ) -> bool {
-        if let Some(feature_attributes) = self.conditions() {
-            feature_attributes.matches(&[GrowthBookAttribute::new(String::from("value"), GrowthBookAttributeValue::from(feature.value))])
+        if let Some(condition) = self.condition() {
+            condition.matches(&[GrowthBookAttribute::new(String::from("value"), GrowthBookAttributeValue::from(feature.value))], saved_groups)
         } else {
             true
         }
     }
 }
+
+pub enum ParentConditionsOutcome {
+    Met,
+    NotMet,
+    Blocked(Box<FeatureResult>),
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn check_parent_conditions(
+    parent_conditions: &[GrowthBookFeatureRuleParentData],
+    feature_name: &str,
+    feature_name_decorate: &[String],
+    user_attributes: &Vec<GrowthBookAttribute>,
+    forced_variations: &ForcedVariations,
+    sticky_bucket_service: &Option<Arc<dyn StickyBucketService>>,
+    experiment_tracker: &Option<Arc<ExperimentTracker>>,
+    saved_groups: &SavedGroups,
+    all_features: &HashMap<String, GrowthBookFeature>,
+) -> ParentConditionsOutcome {
+    for parent in parent_conditions {
+        let parent_feature_name = &parent.id;
+        if feature_name_decorate.contains(parent_feature_name) {
+            return ParentConditionsOutcome::Blocked(Box::new(FeatureResult::cyclic_prerequisite()));
+        }
+
+        let mut updated_decorate = feature_name_decorate.to_vec();
+        updated_decorate.push(String::from(feature_name));
+
+        let parent_response = if let Some(parent_feature) = all_features.get(parent_feature_name) {
+            parent_feature.get_value(
+                parent_feature_name,
+                updated_decorate,
+                user_attributes,
+                forced_variations,
+                sticky_bucket_service,
+                experiment_tracker,
+                saved_groups,
+                all_features,
+            )
+        } else {
+            FeatureResult::unknown_feature()
+        };
+
+        if let (Some(tracker), Some(experiment), Some(experiment_result)) = (experiment_tracker, &parent_response.experiment, &parent_response.experiment_result) {
+            tracker.track(experiment, experiment_result);
+        }
+
+        if parent_response.source == "cyclicPrerequisite" {
+            return ParentConditionsOutcome::Blocked(Box::new(FeatureResult::cyclic_prerequisite()));
+        }
+
+        if !parent.is_met(parent_response, saved_groups) {
+            return if parent.gate {
+                ParentConditionsOutcome::Blocked(Box::new(FeatureResult::prerequisite()))
+            } else {
+                ParentConditionsOutcome::NotMet
+            };
+        }
+    }
+    ParentConditionsOutcome::Met
+}
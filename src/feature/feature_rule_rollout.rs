@@ -1,4 +1,4 @@
-use crate::condition::use_case::ConditionsMatchesAttributes;
+use crate::condition::saved_group::SavedGroups;
 use crate::coverage::model::Coverage;
 use crate::dto::GrowthBookFeatureRuleRollout;
 use crate::extensions::FindGrowthBookAttribute;
@@ -10,9 +10,10 @@ impl GrowthBookFeatureRuleRollout {
         &self,
         feature_name: &str,
         user_attributes: &Vec<GrowthBookAttribute>,
+        saved_groups: &SavedGroups,
     ) -> Option<FeatureResult> {
-        if let Some(feature_attributes) = &self.conditions() {
-            if feature_attributes.matches(user_attributes) {
+        if let Some(condition) = self.condition() {
+            if condition.matches(user_attributes, saved_groups) {
                 self.check_coverage(feature_name, user_attributes)
             } else {
                 None
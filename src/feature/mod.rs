@@ -1,5 +1,5 @@
 pub mod feature_rule_experiment;
 pub mod feature_rule_force;
-mod feature_rule_parent;
+pub mod feature_rule_parent;
 pub mod feature_rule_rollout;
 pub mod use_case;
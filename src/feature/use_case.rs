@@ -1,59 +1,68 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 
+use crate::condition::saved_group::SavedGroups;
 use crate::dto::{GrowthBookFeature, GrowthBookFeatureRule};
+use crate::experiment::forced_variation::ForcedVariations;
+use crate::feature::feature_rule_parent::{check_parent_conditions, ParentConditionsOutcome};
 use crate::model_private::FeatureResult;
 use crate::model_public::GrowthBookAttribute;
+use crate::sticky_bucket::service::StickyBucketService;
+use crate::tracking::ExperimentTracker;
 
 impl GrowthBookFeature {
+    #[allow(clippy::too_many_arguments)]
     pub fn get_value(
         &self,
         feature_name: &str,
         feature_name_decorate: Vec<String>,
         user_attributes: &Vec<GrowthBookAttribute>,
-        forced_variations: &Option<HashMap<String, i64>>,
-        all_features: HashMap<String, GrowthBookFeature>,
+        forced_variations: &ForcedVariations,
+        sticky_bucket_service: &Option<Arc<dyn StickyBucketService>>,
+        experiment_tracker: &Option<Arc<ExperimentTracker>>,
+        saved_groups: &SavedGroups,
+        all_features: &HashMap<String, GrowthBookFeature>,
     ) -> FeatureResult {
         if let Some(rules) = &self.rules {
             for rule in rules {
                 match rule {
                     GrowthBookFeatureRule::Force(it) => {
-                        if let Some(feature) = it.get_match_value(feature_name, user_attributes) {
+                        if let Some(feature) = it.get_match_value(feature_name, user_attributes, saved_groups) {
                             return feature;
                         }
                     },
                     GrowthBookFeatureRule::Rollout(it) => {
-                        if let Some(feature) = it.get_match_value(feature_name, user_attributes) {
+                        if let Some(feature) = it.get_match_value(feature_name, user_attributes, saved_groups) {
                             return feature;
                         }
                     },
                     GrowthBookFeatureRule::Experiment(it) => {
-                        if let Some(feature) = it.get_match_value(feature_name, user_attributes, forced_variations) {
+                        if let Some(feature) = it.get_match_value(
+                            feature_name,
+                            &feature_name_decorate,
+                            user_attributes,
+                            forced_variations,
+                            sticky_bucket_service,
+                            experiment_tracker,
+                            saved_groups,
+                            all_features,
+                        ) {
                             return feature;
                         }
                     },
                     GrowthBookFeatureRule::Parent(it) => {
-                        for parent in &it.parent_conditions {
-                            let parent_feature_name = &parent.id;
-                            if feature_name_decorate.contains(parent_feature_name) {
-                                return FeatureResult::cyclic_prerequisite();
-                            }
-
-                            let mut updated_decorate = feature_name_decorate.clone();
-                            updated_decorate.push(String::from(feature_name));
-
-                            let parent_response = if let Some(parent_feature) = all_features.get(parent_feature_name) {
-                                parent_feature.get_value(parent_feature_name, updated_decorate, user_attributes, forced_variations, all_features.clone())
-                            } else {
-                                FeatureResult::unknown_feature()
-                            };
-
-                            if parent_response.source == "cyclicPrerequisite" {
-                                return FeatureResult::cyclic_prerequisite();
-                            }
-
-                            if !parent.is_met(parent_response) {
-                                return FeatureResult::prerequisite();
-                            }
+                        if let ParentConditionsOutcome::Blocked(feature) = check_parent_conditions(
+                            &it.parent_conditions,
+                            feature_name,
+                            &feature_name_decorate,
+                            user_attributes,
+                            forced_variations,
+                            sticky_bucket_service,
+                            experiment_tracker,
+                            saved_groups,
+                            all_features,
+                        ) {
+                            return *feature;
                         }
                     },
                     GrowthBookFeatureRule::Empty(_) => {
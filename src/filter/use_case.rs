@@ -13,27 +13,27 @@ impl Filter {
         hash_attribute: &str,
         user_attributes: &Vec<GrowthBookAttribute>,
     ) -> bool {
-        for filter in filters.force_array(vec![]) {
-            if let Some(user_value) = user_attributes.find_value(hash_attribute) {
+        filters.force_array(vec![]).iter().any(|filter| {
+            let filter_attribute = filter.get_string("attribute", hash_attribute);
+            if let Some(user_value) = user_attributes.find_value(&filter_attribute) {
                 if let Some(user_weight) = HashCode::hash_code(
                     &user_value.to_string(),
                     &filter.get_string("seed", ""),
-                    HashCodeVersion::from(filter.get("hashVersion").unwrap_or(&Value::from(2)).as_i64().expect("Failed to convert to i64")),
+                    HashCodeVersion::from(filter.get("hashVersion").and_then(|it| it.as_i64()).unwrap_or(2)),
                 ) {
-                    for array in filter.get_array("ranges", vec![]) {
+                    !filter.get_array("ranges", vec![]).iter().any(|array| {
                         let range = Range {
                             start: array[0].force_f32(0.0),
                             end: array[1].force_f32(1.0),
                         };
-                        if range.in_range(&user_weight) {
-                            return false;
-                        }
-                    }
+                        range.in_range(&user_weight)
+                    })
+                } else {
+                    true
                 }
             } else {
-                return true;
+                true
             }
-        }
-        true
+        })
     }
 }
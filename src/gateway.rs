@@ -1,24 +1,36 @@
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
-use reqwest::header::USER_AGENT;
+use reqwest::header::{HeaderMap, ACCEPT, ETAG, IF_NONE_MATCH, USER_AGENT};
+use reqwest::{Response, StatusCode};
 use reqwest_middleware::ClientWithMiddleware;
+use tracing::error;
 
+use crate::cache::FeaturesCacheFile;
 use crate::dto::GrowthBookResponse;
 use crate::env::Environment;
 use crate::error::GrowthbookError;
 use crate::infra::HttpClient;
+use crate::model_public::FeaturesCache;
+use crate::payload::FeaturesPayloadParser;
 
 #[derive(Clone, Debug)]
 pub struct GrowthbookGateway {
     pub url: String,
     pub user_agent: String,
     sdk_key: String,
+    decryption_key: Option<String>,
     pub client: ClientWithMiddleware,
+    streaming_client: ClientWithMiddleware,
+    etag: Arc<Mutex<Option<String>>>,
+    cache: Option<FeaturesCache>,
+    headers: HeaderMap,
 }
 impl GrowthbookGateway {
     pub fn new(
         url: &str,
         sdk_key: &str,
+        decryption_key: Option<&str>,
         timeout: Duration,
     ) -> Result<Self, GrowthbookError> {
         Ok(Self {
@@ -28,21 +40,161 @@ impl GrowthbookGateway {
                 Environment::string_or_default("CARGO_PKG_NAME", "growthbook-rust-sdk"),
                 Environment::string_or_default("CARGO_PKG_VERSION", "1.0.0")
             ),
-            client: HttpClient::create_http_client("growthbook", timeout).map_err(GrowthbookError::from)?,
+            client: HttpClient::create_http_client("growthbook", timeout)?,
+            streaming_client: HttpClient::create_streaming_http_client("growthbook-streaming", timeout)?,
             sdk_key: sdk_key.to_string(),
+            decryption_key: decryption_key.map(String::from),
+            etag: Arc::new(Mutex::new(None)),
+            cache: None,
+            headers: HeaderMap::new(),
         })
     }
 
-    pub async fn get_features(
+    pub fn with_cache(
+        self,
+        cache: Option<FeaturesCache>,
+    ) -> Self {
+        Self { cache, ..self }
+    }
+
+    pub fn with_headers(
+        self,
+        headers: HeaderMap,
+    ) -> Self {
+        Self { headers, ..self }
+    }
+
+    pub async fn get_features_if_modified(&self) -> Result<Option<GrowthBookResponse>, GrowthbookError> {
+        let etag = self.etag();
+        let send_result = self.send(etag.as_deref()).await?;
+        if send_result.status() == StatusCode::NOT_MODIFIED {
+            if let (Some(cache), Some(etag)) = (&self.cache, &etag) {
+                if let Err(e) = FeaturesCacheFile::touch(cache, etag).await {
+                    error!("[growthbook-sdk] problem to writing features cache {:?}", e);
+                }
+            }
+            return Ok(None);
+        }
+
+        self.read_features(send_result).await.map(Some)
+    }
+
+    async fn send(
         &self,
-        sdk_key: Option<&str>,
+        etag: Option<&str>,
+    ) -> Result<Response, GrowthbookError> {
+        let url = format!("{}/api/features/{}", self.url, self.sdk_key);
+        let mut request = self.client.get(url).headers(self.headers.clone()).header(USER_AGENT, self.user_agent.clone());
+        if let Some(etag) = etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        let response = request.send().await.map_err(GrowthbookError::from)?;
+        if response.status().is_success() || response.status() == StatusCode::NOT_MODIFIED {
+            Ok(response)
+        } else {
+            Err(GrowthbookError::from(response))
+        }
+    }
+
+    async fn read_features(
+        &self,
+        send_result: Response,
     ) -> Result<GrowthBookResponse, GrowthbookError> {
-        let sdk = sdk_key.unwrap_or(self.sdk_key.as_str());
-        let url = format!("{}/api/features/{}", self.url, sdk);
-        let send_result = self.client.get(url).header(USER_AGENT, self.user_agent.clone()).send().await.map_err(GrowthbookError::from)?;
+        let etag = send_result.headers().get(ETAG).and_then(|it| it.to_str().ok()).map(String::from);
+        let payload = send_result.text().await.map_err(GrowthbookError::from)?;
+        let response = self.parse_features(&payload)?;
+
+        self.write_cache(&payload, etag.clone()).await;
+        self.set_etag(etag);
+        Ok(response)
+    }
 
-        let response = send_result.json::<GrowthBookResponse>().await.map_err(GrowthbookError::from)?;
+    // streamed payloads carry no etag, so the next poll downloads and caches the full payload again
+    pub async fn read_streamed_features(
+        &self,
+        payload: &str,
+    ) -> Result<GrowthBookResponse, GrowthbookError> {
+        let response = self.parse_features(payload)?;
 
+        self.write_cache(payload, None).await;
+        self.set_etag(None);
         Ok(response)
     }
+
+    async fn write_cache(
+        &self,
+        payload: &str,
+        etag: Option<String>,
+    ) {
+        if let Some(cache) = &self.cache {
+            if let Err(e) = FeaturesCacheFile::write(cache, payload, etag).await {
+                error!("[growthbook-sdk] problem to writing features cache {:?}", e);
+            }
+        }
+    }
+
+    pub fn load_cached_features(&self) -> Option<GrowthBookResponse> {
+        let cache = self.cache.as_ref()?;
+        let cached_features = FeaturesCacheFile::read(cache).and_then(|cached| {
+            let response = self.parse_features(cached.payload.get())?;
+            Ok((response, cached.etag))
+        });
+
+        match cached_features {
+            Ok((response, etag)) => {
+                self.set_etag(etag);
+                Some(response)
+            },
+            Err(e) => {
+                error!("[growthbook-sdk] problem to reading features cache {:?}", e);
+                None
+            },
+        }
+    }
+
+    pub async fn subscribe(&self) -> Result<Response, GrowthbookError> {
+        let url = format!("{}/sub/{}", self.url, self.sdk_key);
+        let response = self
+            .streaming_client
+            .get(url)
+            .headers(self.headers.clone())
+            .header(USER_AGENT, self.user_agent.clone())
+            .header(ACCEPT, "text/event-stream")
+            .send()
+            .await
+            .map_err(GrowthbookError::from)?;
+
+        if response.status().is_success() {
+            Ok(response)
+        } else {
+            Err(GrowthbookError::from(response))
+        }
+    }
+
+    pub fn parse_features(
+        &self,
+        payload: &str,
+    ) -> Result<GrowthBookResponse, GrowthbookError> {
+        FeaturesPayloadParser::parse(payload, self.decryption_key.as_deref())
+    }
+
+    fn etag(&self) -> Option<String> {
+        match self.etag.lock() {
+            Ok(etag) => etag.clone(),
+            Err(e) => {
+                error!("[growthbook-sdk] problem to reading features etag {:?}", e);
+                None
+            },
+        }
+    }
+
+    fn set_etag(
+        &self,
+        etag: Option<String>,
+    ) {
+        match self.etag.lock() {
+            Ok(mut current) => *current = etag,
+            Err(e) => error!("[growthbook-sdk] problem to writing features etag {:?}", e),
+        }
+    }
 }
@@ -1,13 +1,33 @@
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::sync::Arc;
 
+use serde_json::Value;
+
+use crate::condition::saved_group::SavedGroups;
 use crate::dto::GrowthBookFeature;
+use crate::experiment::forced_variation::ForcedVariations;
 use crate::model_private::FeatureResult;
-use crate::model_public::GrowthBookAttribute;
+use crate::model_public::{Experiment, ExperimentResult, GrowthBookAttribute, UrlRedirect};
+use crate::sticky_bucket::service::StickyBucketService;
+use crate::tracking::{ExperimentTracker, FeatureUsageTracker};
+use crate::url_redirect::use_case::UrlTargeting;
+
+static EMPTY_ATTRIBUTES: Vec<GrowthBookAttribute> = Vec::new();
 
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct GrowthBook {
     pub forced_variations: Option<HashMap<String, i64>>,
-    pub features: HashMap<String, GrowthBookFeature>,
+    pub features: Arc<HashMap<String, GrowthBookFeature>>,
+    pub saved_groups: Arc<SavedGroups>,
+    pub experiments: Arc<Vec<Experiment>>,
+    pub sticky_bucket_service: Option<Arc<dyn StickyBucketService>>,
+    pub experiment_tracker: Option<Arc<ExperimentTracker>>,
+    pub feature_usage_tracker: Option<Arc<FeatureUsageTracker>>,
+    pub query_string_overrides: bool,
+    pub forced_features: Arc<HashMap<String, Value>>,
+    pub forced_variation_overrides: Arc<HashMap<String, i64>>,
+    pub attributes: Arc<Vec<GrowthBookAttribute>>,
 }
 
 impl GrowthBook {
@@ -15,24 +35,151 @@ impl GrowthBook {
         &self,
         flag_name: &str,
         option_user_attributes: &Option<Vec<GrowthBookAttribute>>,
+        url: Option<&str>,
     ) -> FeatureResult {
+        let result = self.evaluate(flag_name, option_user_attributes, url);
+        if let Some(tracker) = &self.feature_usage_tracker {
+            tracker.track(flag_name, &result);
+        }
+        result
+    }
+
+    fn evaluate(
+        &self,
+        flag_name: &str,
+        option_user_attributes: &Option<Vec<GrowthBookAttribute>>,
+        url: Option<&str>,
+    ) -> FeatureResult {
+        if let Some(value) = self.forced_features.get(flag_name) {
+            return FeatureResult::override_value(value.clone());
+        }
+
         if let Some(feature) = self.features.get(flag_name) {
-            let user_attributes = &option_user_attributes.clone().unwrap_or_default();
-            feature.get_value(flag_name, vec![], user_attributes, &self.forced_variations, self.features.clone())
+            let user_attributes = self.attributes(option_user_attributes);
+            let result = feature.get_value(
+                flag_name,
+                vec![],
+                &user_attributes,
+                &self.forced_variations(url),
+                &self.sticky_bucket_service,
+                &self.experiment_tracker,
+                &self.saved_groups,
+                &self.features,
+            );
+            if let (Some(experiment), Some(experiment_result)) = (&result.experiment, &result.experiment_result) {
+                self.track(experiment, experiment_result);
+            }
+            result
         } else {
             FeatureResult::unknown_feature()
         }
     }
+
+    pub fn run(
+        &self,
+        experiment: &Experiment,
+        option_user_attributes: &Option<Vec<GrowthBookAttribute>>,
+        url: Option<&str>,
+    ) -> ExperimentResult {
+        let user_attributes = self.attributes(option_user_attributes);
+        let result = experiment.run(
+            None,
+            url,
+            &user_attributes,
+            &self.forced_variations(url),
+            &self.sticky_bucket_service,
+            &self.experiment_tracker,
+            &self.saved_groups,
+            &self.features,
+        );
+        self.track(experiment, &result);
+        result
+    }
+
+    pub fn redirect(
+        &self,
+        url: &str,
+        option_user_attributes: &Option<Vec<GrowthBookAttribute>>,
+    ) -> Option<UrlRedirect> {
+        for experiment in self.experiments.iter() {
+            let Some(url_patterns) = &experiment.url_patterns else {
+                continue;
+            };
+            if !experiment.variations.iter().any(|variation| variation.get("urlRedirect").is_some()) || !UrlTargeting::is_targeted(url, url_patterns) {
+                continue;
+            }
+
+            let experiment_result = self.run(experiment, option_user_attributes, Some(url));
+            let Some(url_redirect) = experiment_result.value.get("urlRedirect").and_then(Value::as_str).filter(|it| !it.is_empty()) else {
+                continue;
+            };
+            if !experiment_result.in_experiment {
+                continue;
+            }
+
+            let redirect_url = if experiment.persist_query_string.unwrap_or(false) {
+                UrlTargeting::merge_query_strings(url, url_redirect)
+            } else {
+                String::from(url_redirect)
+            };
+
+            // the original url already matches the redirect target, so redirecting would loop
+            if UrlTargeting::is_targeted(&redirect_url, url_patterns) {
+                continue;
+            }
+
+            return Some(UrlRedirect {
+                url: redirect_url,
+                experiment: experiment.clone(),
+                experiment_result,
+            });
+        }
+        None
+    }
+
+    fn forced_variations<'a>(
+        &'a self,
+        url: Option<&'a str>,
+    ) -> ForcedVariations<'a> {
+        ForcedVariations::new(&self.forced_variations, url.filter(|_| self.query_string_overrides)).with_overrides(&self.forced_variation_overrides)
+    }
+
+    #[allow(clippy::owned_cow)]
+    fn attributes<'a>(
+        &'a self,
+        option_user_attributes: &'a Option<Vec<GrowthBookAttribute>>,
+    ) -> Cow<'a, Vec<GrowthBookAttribute>> {
+        let user_attributes = option_user_attributes.as_ref().unwrap_or(&EMPTY_ATTRIBUTES);
+        if self.attributes.is_empty() {
+            return Cow::Borrowed(user_attributes);
+        }
+
+        let mut attributes: Vec<GrowthBookAttribute> = self.attributes.iter().filter(|global| user_attributes.iter().all(|user| user.key != global.key)).cloned().collect();
+        attributes.extend(user_attributes.iter().cloned());
+        Cow::Owned(attributes)
+    }
+
+    fn track(
+        &self,
+        experiment: &Experiment,
+        experiment_result: &ExperimentResult,
+    ) {
+        if let Some(tracker) = &self.experiment_tracker {
+            tracker.track(experiment, experiment_result);
+        }
+    }
 }
 
 #[cfg(test)]
 mod test {
     use std::collections::HashMap;
     use std::fs;
+    use std::sync::Arc;
 
     use serde::Deserialize;
     use serde_json::Value;
 
+    use crate::condition::saved_group::SavedGroups;
     use crate::dto::GrowthBookFeature;
     use crate::extensions::JsonHelper;
     use crate::growthbook::GrowthBook;
@@ -48,13 +195,22 @@ mod test {
             let gb_test = serde_json::from_value::<GrowthBookForTest>(feature.feature.clone()).unwrap_or_else(|_| panic!("Failed to convert to GrowthBookForTest case='{}'", feature.name));
             let gb = GrowthBook {
                 forced_variations: feature.forced_variations.clone(),
-                features: gb_test.features.unwrap_or_default(),
+                features: Arc::new(gb_test.features.unwrap_or_default()),
+                saved_groups: Arc::new(SavedGroups::new(gb_test.saved_groups.unwrap_or_default())),
+                experiments: Arc::new(vec![]),
+                query_string_overrides: false,
+                sticky_bucket_service: None,
+                experiment_tracker: None,
+                feature_usage_tracker: None,
+                forced_features: Arc::new(HashMap::new()),
+                forced_variation_overrides: Arc::new(HashMap::new()),
+                attributes: Arc::new(vec![]),
             };
             let user_attributes = feature
                 .attributes
                 .clone()
                 .map(|attr| GrowthBookAttribute::from(attr).expect("Failed to convert to GrowthBookAttribute"));
-            let result = gb.check(feature.feature_name.as_str(), &user_attributes);
+            let result = gb.check(feature.feature_name.as_str(), &user_attributes, None);
             validate_result(feature, result);
         }
 
@@ -92,6 +248,7 @@ mod test {
     #[serde(rename_all = "camelCase")]
     pub struct GrowthBookForTest {
         pub features: Option<HashMap<String, GrowthBookFeature>>,
+        pub saved_groups: Option<HashMap<String, Vec<Value>>>,
     }
 
     #[derive(Clone)]
@@ -19,4 +19,15 @@ impl HttpClient {
             .build();
         Ok(client)
     }
+
+    pub fn create_streaming_http_client(
+        name: &str,
+        connect_timeout_duration: Duration,
+    ) -> Result<ClientWithMiddleware, GrowthbookError> {
+        let client = ClientBuilder::new(Client::builder().connect_timeout(connect_timeout_duration).build().map_err(GrowthbookError::from)?)
+            .with_init(Extension(OtelName(String::from(name).into())))
+            .with(TracingMiddleware::default())
+            .build();
+        Ok(client)
+    }
 }
@@ -1,17 +1,28 @@
+pub mod builder;
+mod cache;
 pub mod client;
 mod condition;
 pub mod coverage;
+mod decryption;
 pub mod dto;
 mod env;
 pub mod error;
+mod experiment;
 mod extensions;
 mod feature;
 pub mod filter;
 mod gateway;
-mod growthbook;
+pub mod growthbook;
 mod hash;
 mod infra;
 mod model_private;
 pub mod model_public;
 pub mod namespace;
+mod payload;
 mod range;
+mod refresh;
+pub mod source;
+pub mod sticky_bucket;
+mod streaming;
+pub mod tracking;
+mod url_redirect;
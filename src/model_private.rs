@@ -3,6 +3,7 @@ use serde_json::Value;
 
 use crate::error::GrowthbookError;
 use crate::extensions::JsonHelper;
+use crate::model_public::{Experiment, ExperimentResult};
 
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -15,38 +16,6 @@ pub struct FeatureResult {
     pub source: String,
 }
 
-#[derive(Serialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Experiment {
-    pub name: Option<String>,
-    pub seed: Option<String>,
-    pub hash_version: Option<i64>,
-    pub hash_attribute: Option<String>,
-    pub namespace: Option<Vec<Value>>,
-    pub coverage: Option<f32>,
-    pub ranges: Option<Vec<Vec<f32>>>,
-    pub meta: Option<Value>,
-    pub filters: Option<Value>,
-    pub variations: Vec<Value>,
-    pub weights: Option<Vec<f32>>,
-    pub condition: Option<Value>,
-}
-
-#[derive(Serialize)]
-#[serde(rename_all = "camelCase")]
-pub struct ExperimentResult {
-    pub feature_id: String,
-    pub value: Value,
-    pub variation_id: i64,
-    pub in_experiment: bool,
-    pub hash_used: bool,
-    pub hash_attribute: Option<String>,
-    pub hash_value: Option<Value>,
-    pub bucket: Option<f32>,
-    pub key: String,
-    pub sticky_bucket_used: bool,
-}
-
 impl FeatureResult {
     pub fn value_as<T>(&self) -> Result<T, GrowthbookError>
     where
@@ -66,6 +35,18 @@ impl FeatureResult {
             source: String::from("force"),
         }
     }
+    pub fn override_value(value: Value) -> Self {
+        let is_on = is_on(&value);
+        FeatureResult {
+            value,
+            on: is_on,
+            off: !is_on,
+            experiment: None,
+            experiment_result: None,
+            source: String::from("override"),
+        }
+    }
+
     pub fn experiment(
         value: Value,
         experiment: Experiment,
@@ -132,7 +113,7 @@ impl FeatureResult {
 fn is_on(value: &Value) -> bool {
     let is_on = if value.is_null() {
         false
-    } else if (value.is_number() && value.force_f64(-1.0) != 0.0) || (value.is_string() && value.force_string("any") != "") {
+    } else if (value.is_number() && value.force_f64(-1.0) != 0.0) || (value.is_string() && !value.force_string("any").is_empty()) {
         true
     } else if value.is_boolean() {
         value.as_bool().unwrap_or(false)
@@ -1,9 +1,22 @@
 use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::Duration;
 
+use lazy_static::lazy_static;
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
 
+pub use crate::condition::model::Condition;
+use crate::dto::GrowthBookFeatureRuleParentData;
 use crate::error::{GrowthbookError, GrowthbookErrorCode};
+pub use crate::model_private::FeatureResult;
+use crate::url_redirect::use_case::UrlMatcher;
+
+lazy_static! {
+    static ref DECIMAL_NUMBER: Regex = Regex::new("^[+-]?(\\d+\\.?\\d*|\\.\\d+)([eE][+-]?\\d+)?$").expect("Failed to compile decimal number regex");
+}
 
 #[derive(Clone, PartialEq, Debug)]
 pub struct GrowthBookAttribute {
@@ -14,6 +27,7 @@ pub struct GrowthBookAttribute {
 #[derive(Clone, PartialEq, Debug)]
 pub enum GrowthBookAttributeValue {
     Empty,
+    Null,
     String(String),
     Int(i64),
     Float(f64),
@@ -22,6 +36,160 @@ pub enum GrowthBookAttributeValue {
     Object(Vec<GrowthBookAttribute>),
 }
 
+#[derive(Serialize, Deserialize, Clone, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Experiment {
+    pub key: String,
+    pub variations: Vec<Value>,
+    pub name: Option<String>,
+    pub active: Option<bool>,
+    pub force: Option<i64>,
+    pub seed: Option<String>,
+    pub hash_version: Option<i64>,
+    pub hash_attribute: Option<String>,
+    pub fallback_attribute: Option<String>,
+    pub namespace: Option<Vec<Value>>,
+    pub coverage: Option<f32>,
+    pub ranges: Option<Vec<Vec<f32>>>,
+    pub meta: Option<Value>,
+    pub filters: Option<Value>,
+    pub weights: Option<Vec<f32>>,
+    pub condition: Option<Condition>,
+    pub parent_conditions: Option<Vec<GrowthBookFeatureRuleParentData>>,
+    pub bucket_version: Option<i64>,
+    pub min_bucket_version: Option<i64>,
+    pub disable_sticky_bucketing: Option<bool>,
+    pub url_patterns: Option<Vec<UrlPattern>>,
+    pub persist_query_string: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UrlPattern {
+    #[serde(rename = "type")]
+    pub pattern_type: String,
+    pub include: Option<bool>,
+    pub pattern: String,
+    #[serde(skip)]
+    matcher: OnceLock<UrlMatcher>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UrlRedirect {
+    pub url: String,
+    pub experiment: Experiment,
+    pub experiment_result: ExperimentResult,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum RefreshStrategy {
+    #[default]
+    Polling,
+    Streaming,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BackoffPolicy {
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+    pub multiplier: f64,
+    pub jitter: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum FeaturesPayload {
+    Json(String),
+    Value(Value),
+}
+
+impl From<&str> for FeaturesPayload {
+    fn from(payload: &str) -> Self {
+        FeaturesPayload::Json(String::from(payload))
+    }
+}
+
+impl From<String> for FeaturesPayload {
+    fn from(payload: String) -> Self {
+        FeaturesPayload::Json(payload)
+    }
+}
+
+impl From<Value> for FeaturesPayload {
+    fn from(payload: Value) -> Self {
+        FeaturesPayload::Value(payload)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FeaturesCache {
+    pub path: PathBuf,
+    pub max_staleness: Option<Duration>,
+}
+
+/// Settings for `GrowthBookClient::new_non_blocking`; every field is optional and `Default` starts
+/// polling with no fallback snapshot.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NonBlockingOptions {
+    /// Key for encrypted payloads, none by default.
+    pub decryption_key: Option<String>,
+    /// Features served until the first fetch succeeds, none by default.
+    pub bootstrap: Option<FeaturesPayload>,
+    /// File cache read at startup, preferred over `bootstrap` when fresh enough. Disabled by default.
+    pub cache: Option<FeaturesCache>,
+    /// `RefreshStrategy::Polling` by default.
+    pub refresh_strategy: RefreshStrategy,
+    /// Polling interval, `GB_UPDATE_INTERVAL` seconds or 60 seconds by default.
+    pub update_interval: Option<Duration>,
+    /// Request timeout, `GB_HTTP_CLIENT_TIMEOUT` seconds or 10 seconds by default.
+    pub http_timeout: Option<Duration>,
+}
+
+impl Default for BackoffPolicy {
+    fn default() -> Self {
+        BackoffPolicy {
+            initial_delay: Duration::from_secs(5),
+            max_delay: Duration::from_secs(300),
+            multiplier: 2.0,
+            jitter: 0.1,
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ExperimentResult {
+    pub feature_id: String,
+    pub value: Value,
+    pub variation_id: i64,
+    pub in_experiment: bool,
+    pub hash_used: bool,
+    pub hash_attribute: Option<String>,
+    pub hash_value: Option<Value>,
+    pub bucket: Option<f32>,
+    pub key: String,
+    pub sticky_bucket_used: bool,
+}
+
+impl UrlPattern {
+    pub fn new(
+        pattern_type: &str,
+        pattern: &str,
+        include: Option<bool>,
+    ) -> Self {
+        UrlPattern {
+            pattern_type: String::from(pattern_type),
+            include,
+            pattern: String::from(pattern),
+            matcher: OnceLock::new(),
+        }
+    }
+
+    pub(crate) fn matcher(&self) -> &UrlMatcher {
+        self.matcher.get_or_init(|| UrlMatcher::compile(self))
+    }
+}
+
 impl GrowthBookAttribute {
     pub fn new(
         key: String,
@@ -53,19 +221,24 @@ impl GrowthBookAttribute {
 
 impl GrowthBookAttributeValue {
     pub fn is_number(&self) -> bool {
-        if let Ok(regex) = Regex::new("\\d+") {
-            regex.is_match(&self.to_string().replace('.', ""))
-        } else {
-            false
-        }
+        matches!(self, GrowthBookAttributeValue::Int(_) | GrowthBookAttributeValue::Float(_))
     }
+
+    // mirrors javascript number coercion, where NaN is None
     pub fn as_f64(&self) -> Option<f64> {
-        self.to_string().replace('.', "").parse::<f64>().ok()
+        match self {
+            GrowthBookAttributeValue::Int(it) => Some(*it as f64),
+            GrowthBookAttributeValue::Float(it) => Some(*it),
+            GrowthBookAttributeValue::Bool(it) => Some(if *it { 1.0 } else { 0.0 }),
+            GrowthBookAttributeValue::String(it) => string_to_f64(it),
+            _ => None,
+        }
     }
 
     pub fn to_value(&self) -> Value {
         match self {
             GrowthBookAttributeValue::Empty => Value::Null,
+            GrowthBookAttributeValue::Null => Value::Null,
             GrowthBookAttributeValue::String(it) => Value::from(it.clone()),
             GrowthBookAttributeValue::Int(it) => Value::from(*it),
             GrowthBookAttributeValue::Float(it) => Value::from(*it),
@@ -82,6 +255,25 @@ impl GrowthBookAttributeValue {
     }
 }
 
+fn string_to_f64(value: &str) -> Option<f64> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Some(0.0);
+    }
+
+    let radix = [("0x", 16), ("0X", 16), ("0o", 8), ("0O", 8), ("0b", 2), ("0B", 2)];
+    if let Some((digits, radix)) = radix.iter().find_map(|(prefix, radix)| value.strip_prefix(prefix).map(|digits| (digits, *radix))) {
+        return u64::from_str_radix(digits, radix).ok().map(|it| it as f64);
+    }
+
+    match value {
+        "Infinity" | "+Infinity" => Some(f64::INFINITY),
+        "-Infinity" => Some(f64::NEG_INFINITY),
+        it if DECIMAL_NUMBER.is_match(it) => it.parse::<f64>().ok(),
+        _ => None,
+    }
+}
+
 impl From<Value> for GrowthBookAttributeValue {
     fn from(value: Value) -> Self {
         if value.is_string() {
@@ -90,24 +282,15 @@ impl From<Value> for GrowthBookAttributeValue {
             GrowthBookAttributeValue::Bool(value.as_bool().unwrap_or_default())
         } else if value.is_i64() {
             GrowthBookAttributeValue::Int(value.as_i64().unwrap_or_default())
-        } else if value.is_f64() {
+        } else if value.is_number() {
             GrowthBookAttributeValue::Float(value.as_f64().unwrap_or_default())
         } else if value.is_array() {
             let vec: Vec<GrowthBookAttributeValue> = value.as_array().unwrap_or(&vec![]).iter().map(|item| GrowthBookAttributeValue::from(item.clone())).collect();
             GrowthBookAttributeValue::Array(vec)
+        } else if let Some(map) = value.as_object() {
+            GrowthBookAttributeValue::Object(map.iter().map(|(k, v)| GrowthBookAttribute::new(k.clone(), GrowthBookAttributeValue::from(v.clone()))).collect())
         } else {
-            let objects: Vec<_> = value
-                .as_object()
-                .unwrap_or(&Map::new())
-                .iter()
-                .map(|(k, v)| GrowthBookAttribute::new(k.clone(), GrowthBookAttributeValue::from(v.clone())))
-                .collect();
-
-            if objects.is_empty() {
-                GrowthBookAttributeValue::Empty
-            } else {
-                GrowthBookAttributeValue::Object(objects)
-            }
+            GrowthBookAttributeValue::Null
         }
     }
 }
@@ -119,6 +302,7 @@ impl Display for GrowthBookAttributeValue {
     ) -> std::fmt::Result {
         let message = match self {
             GrowthBookAttributeValue::Empty => String::new(),
+            GrowthBookAttributeValue::Null => String::new(),
             GrowthBookAttributeValue::Array(it) => it.iter().fold(String::new(), |acc, value| format!("{acc}{}", value)),
             GrowthBookAttributeValue::Object(it) => it.iter().fold(String::new(), |acc, att| format!("{acc}{}", att.value)),
             GrowthBookAttributeValue::String(it) => it.clone(),
@@ -0,0 +1,102 @@
+use crate::decryption::Decryption;
+use crate::dto::GrowthBookResponse;
+use crate::error::{GrowthbookError, GrowthbookErrorCode};
+use crate::model_public::FeaturesPayload;
+
+pub struct FeaturesPayloadParser;
+
+impl FeaturesPayloadParser {
+    pub fn parse(
+        payload: &str,
+        decryption_key: Option<&str>,
+    ) -> Result<GrowthBookResponse, GrowthbookError> {
+        let response = serde_json::from_str::<GrowthBookResponse>(payload).map_err(GrowthbookError::from)?;
+
+        decrypt(response, decryption_key)
+    }
+
+    pub fn parse_payload(
+        payload: FeaturesPayload,
+        decryption_key: Option<&str>,
+    ) -> Result<GrowthBookResponse, GrowthbookError> {
+        match payload {
+            FeaturesPayload::Json(json) => Self::parse(&json, decryption_key),
+            FeaturesPayload::Value(value) => {
+                let response = serde_json::from_value::<GrowthBookResponse>(value).map_err(GrowthbookError::from)?;
+                decrypt(response, decryption_key)
+            },
+        }
+    }
+}
+
+fn decrypt(
+    response: GrowthBookResponse,
+    decryption_key: Option<&str>,
+) -> Result<GrowthBookResponse, GrowthbookError> {
+    let response = decrypt_features(response, decryption_key)?;
+    let response = decrypt_saved_groups(response, decryption_key)?;
+    decrypt_experiments(response, decryption_key)
+}
+
+fn decrypt_features(
+    response: GrowthBookResponse,
+    decryption_key: Option<&str>,
+) -> Result<GrowthBookResponse, GrowthbookError> {
+    if let Some(encrypted_features) = &response.encrypted_features {
+        let decrypted = Decryption::decrypt(encrypted_features, required_decryption_key(decryption_key, "features")?)?;
+        let features = serde_json::from_str(&decrypted).map_err(GrowthbookError::from)?;
+        Ok(GrowthBookResponse {
+            features,
+            encrypted_features: None,
+            ..response
+        })
+    } else {
+        Ok(response)
+    }
+}
+
+fn decrypt_saved_groups(
+    response: GrowthBookResponse,
+    decryption_key: Option<&str>,
+) -> Result<GrowthBookResponse, GrowthbookError> {
+    if let Some(encrypted_saved_groups) = &response.encrypted_saved_groups {
+        let decrypted = Decryption::decrypt(encrypted_saved_groups, required_decryption_key(decryption_key, "saved groups")?)?;
+        let saved_groups = serde_json::from_str(&decrypted).map_err(GrowthbookError::from)?;
+        Ok(GrowthBookResponse {
+            saved_groups,
+            encrypted_saved_groups: None,
+            ..response
+        })
+    } else {
+        Ok(response)
+    }
+}
+
+fn decrypt_experiments(
+    response: GrowthBookResponse,
+    decryption_key: Option<&str>,
+) -> Result<GrowthBookResponse, GrowthbookError> {
+    if let Some(encrypted_experiments) = &response.encrypted_experiments {
+        let decrypted = Decryption::decrypt(encrypted_experiments, required_decryption_key(decryption_key, "experiments")?)?;
+        let experiments = serde_json::from_str(&decrypted).map_err(GrowthbookError::from)?;
+        Ok(GrowthBookResponse {
+            experiments,
+            encrypted_experiments: None,
+            ..response
+        })
+    } else {
+        Ok(response)
+    }
+}
+
+fn required_decryption_key<'a>(
+    decryption_key: Option<&'a str>,
+    payload_section: &str,
+) -> Result<&'a str, GrowthbookError> {
+    decryption_key.ok_or_else(|| {
+        GrowthbookError::new(
+            GrowthbookErrorCode::DecryptionError,
+            &format!("Received encrypted {payload_section} but no decryption key was configured"),
+        )
+    })
+}
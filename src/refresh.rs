@@ -0,0 +1,217 @@
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use arc_swap::{ArcSwap, ArcSwapOption};
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use tokio::runtime::Handle;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tracing::error;
+
+use crate::error::{GrowthbookError, GrowthbookErrorCode};
+use crate::model_public::BackoffPolicy;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Readiness {
+    Pending,
+    Ready,
+    Stopped,
+}
+
+pub struct RefreshState {
+    backoff_policy: Arc<ArcSwap<BackoffPolicy>>,
+    failures: AtomicU32,
+    serving_cache: AtomicBool,
+    last_success: ArcSwapOption<DateTime<Utc>>,
+    readiness: watch::Sender<Readiness>,
+}
+
+impl Default for RefreshState {
+    fn default() -> Self {
+        RefreshState {
+            backoff_policy: Arc::new(ArcSwap::from_pointee(BackoffPolicy::default())),
+            failures: AtomicU32::new(0),
+            serving_cache: AtomicBool::new(false),
+            last_success: ArcSwapOption::empty(),
+            readiness: watch::Sender::new(Readiness::Pending),
+        }
+    }
+}
+
+impl RefreshState {
+    pub fn set_backoff_policy(
+        &self,
+        backoff_policy: BackoffPolicy,
+    ) {
+        self.backoff_policy.store(Arc::new(backoff_policy));
+    }
+
+    // sources that retry on their own read the policy through this handle, so later changes reach them
+    pub(crate) fn shared_backoff_policy(&self) -> Arc<ArcSwap<BackoffPolicy>> {
+        Arc::clone(&self.backoff_policy)
+    }
+
+    pub fn failures(&self) -> u32 {
+        self.failures.load(Ordering::Relaxed)
+    }
+
+    pub fn last_success(&self) -> Option<DateTime<Utc>> {
+        self.last_success.load().as_deref().copied()
+    }
+
+    pub fn is_serving_cache(&self) -> bool {
+        self.serving_cache.load(Ordering::Relaxed)
+    }
+
+    pub fn record_cache_load(&self) {
+        self.serving_cache.store(true, Ordering::Relaxed);
+    }
+
+    pub fn record_success(&self) {
+        self.failures.store(0, Ordering::Relaxed);
+        self.serving_cache.store(false, Ordering::Relaxed);
+        self.last_success.store(Some(Arc::new(Utc::now())));
+        self.readiness.send_if_modified(|readiness| {
+            let pending = *readiness == Readiness::Pending;
+            *readiness = Readiness::Ready;
+            pending
+        });
+    }
+
+    // wakes up ready() waiters when the first fetch can no longer be expected to succeed on its own
+    pub fn record_stop(&self) {
+        self.readiness.send_if_modified(|readiness| {
+            let pending = *readiness == Readiness::Pending;
+            if pending {
+                *readiness = Readiness::Stopped;
+            }
+            pending
+        });
+    }
+
+    pub fn is_ready(&self) -> bool {
+        *self.readiness.borrow() == Readiness::Ready
+    }
+
+    pub async fn ready(
+        &self,
+        timeout: Option<Duration>,
+    ) -> Result<(), GrowthbookError> {
+        let mut receiver = self.readiness.subscribe();
+        let wait = receiver.wait_for(|readiness| *readiness != Readiness::Pending);
+        let readiness = match timeout {
+            Some(timeout) => match tokio::time::timeout(timeout, wait).await {
+                Ok(readiness) => readiness.map(|it| *it),
+                Err(_) => {
+                    return Err(GrowthbookError::new(
+                        GrowthbookErrorCode::GrowthbookClientNotReady,
+                        &format!("Features were not fetched within {timeout:?}"),
+                    ))
+                },
+            },
+            None => wait.await.map(|it| *it),
+        };
+
+        match readiness {
+            Ok(Readiness::Ready) => Ok(()),
+            _ => Err(GrowthbookError::new(
+                GrowthbookErrorCode::GrowthbookClientNotReady,
+                "Features refresh stopped before the first successful fetch",
+            )),
+        }
+    }
+
+    pub fn record_failure(&self) -> u32 {
+        self.failures.fetch_add(1, Ordering::Relaxed).saturating_add(1)
+    }
+
+    pub fn failure_delay(
+        &self,
+        failures: u32,
+        interval: Duration,
+    ) -> Duration {
+        backoff_delay(&self.backoff_policy.load(), failures, interval)
+    }
+}
+
+pub fn backoff_delay(
+    policy: &BackoffPolicy,
+    failures: u32,
+    interval: Duration,
+) -> Duration {
+    let base = policy.initial_delay.min(interval).as_secs_f64();
+    let exponent = i32::try_from(failures.saturating_sub(1)).unwrap_or(i32::MAX);
+    let seconds = base * policy.multiplier.max(1.0).powi(exponent);
+    jittered(seconds.min(policy.max_delay.as_secs_f64().max(base)), policy.jitter)
+}
+
+pub fn jittered(
+    seconds: f64,
+    jitter: f64,
+) -> Duration {
+    let jitter = if jitter.is_nan() { 0.0 } else { jitter.clamp(0.0, 1.0) };
+    let factor = 1.0 + rand::thread_rng().gen_range(-jitter..=jitter);
+    Duration::try_from_secs_f64(seconds * factor).unwrap_or(Duration::MAX)
+}
+
+#[derive(Default)]
+pub struct RefreshTask {
+    handle: Mutex<Option<JoinHandle<()>>>,
+    refresh_state: Option<Arc<RefreshState>>,
+}
+
+impl RefreshTask {
+    pub fn spawn(
+        refresh_state: Arc<RefreshState>,
+        task: impl Future<Output = ()> + Send + 'static,
+    ) -> Result<Self, GrowthbookError> {
+        let runtime = Handle::try_current().map_err(|e| GrowthbookError::new(GrowthbookErrorCode::MissingTokioRuntime, &format!("Refreshing features in background requires a tokio runtime: {e}")))?;
+        Ok(RefreshTask {
+            handle: Mutex::new(Some(runtime.spawn(task))),
+            refresh_state: Some(refresh_state),
+        })
+    }
+
+    pub async fn shutdown(&self) {
+        let handle = match self.handle.lock() {
+            Ok(mut handle) => handle.take(),
+            Err(e) => {
+                error!("[growthbook-sdk] problem to stop refresh task {:?}", e);
+                None
+            },
+        };
+        if let Some(handle) = handle {
+            handle.abort();
+            let _ = handle.await;
+        }
+        self.record_stop();
+    }
+
+    pub fn is_running(&self) -> bool {
+        match self.handle.lock() {
+            Ok(handle) => handle.as_ref().is_some_and(|it| !it.is_finished()),
+            Err(e) => {
+                error!("[growthbook-sdk] problem to read refresh task {:?}", e);
+                false
+            },
+        }
+    }
+
+    fn record_stop(&self) {
+        if let Some(refresh_state) = &self.refresh_state {
+            refresh_state.record_stop();
+        }
+    }
+}
+
+impl Drop for RefreshTask {
+    fn drop(&mut self) {
+        if let Some(handle) = self.handle.get_mut().ok().and_then(Option::take) {
+            handle.abort();
+        }
+        self.record_stop();
+    }
+}
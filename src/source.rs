@@ -0,0 +1,305 @@
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+use arc_swap::ArcSwap;
+use async_trait::async_trait;
+use reqwest::Response;
+use tokio::time::{sleep, timeout};
+use tracing::{error, warn};
+
+use crate::dto::GrowthBookResponse;
+use crate::error::GrowthbookError;
+use crate::gateway::GrowthbookGateway;
+use crate::model_public::{BackoffPolicy, FeaturesPayload};
+use crate::payload::FeaturesPayloadParser;
+use crate::refresh::{backoff_delay, jittered};
+use crate::streaming::{ServerSentEvent, ServerSentEventParser};
+
+// a stream silent for this many refresh intervals is considered dead, heartbeats keep healthy ones alive
+const STREAM_IDLE_INTERVALS: u32 = 3;
+
+// The first call returns the initial snapshot right away, later calls wait for the next update.
+// Ok(None) means the source was checked and nothing changed.
+#[async_trait]
+pub trait FeatureSource: Send {
+    async fn next(&mut self) -> Result<Option<GrowthBookResponse>, GrowthbookError>;
+}
+
+pub struct HttpPollingSource {
+    gateway: GrowthbookGateway,
+    interval: Duration,
+    fetched: bool,
+    backoff_policy: Arc<ArcSwap<BackoffPolicy>>,
+}
+
+impl HttpPollingSource {
+    pub fn new(
+        api_url: &str,
+        sdk_key: &str,
+        decryption_key: Option<&str>,
+        update_interval: Duration,
+        http_timeout: Duration,
+    ) -> Result<Self, GrowthbookError> {
+        let gateway = GrowthbookGateway::new(api_url, sdk_key, decryption_key, http_timeout)?;
+        Ok(Self::from_gateway(gateway, update_interval))
+    }
+
+    pub(crate) fn from_gateway(
+        gateway: GrowthbookGateway,
+        interval: Duration,
+    ) -> Self {
+        HttpPollingSource {
+            gateway,
+            interval,
+            fetched: false,
+            backoff_policy: Arc::new(ArcSwap::from_pointee(BackoffPolicy::default())),
+        }
+    }
+
+    pub fn with_backoff_policy(
+        self,
+        backoff_policy: BackoffPolicy,
+    ) -> Self {
+        self.with_shared_backoff_policy(Arc::new(ArcSwap::from_pointee(backoff_policy)))
+    }
+
+    pub(crate) fn with_shared_backoff_policy(
+        self,
+        backoff_policy: Arc<ArcSwap<BackoffPolicy>>,
+    ) -> Self {
+        HttpPollingSource { backoff_policy, ..self }
+    }
+}
+
+#[async_trait]
+impl FeatureSource for HttpPollingSource {
+    async fn next(&mut self) -> Result<Option<GrowthBookResponse>, GrowthbookError> {
+        if self.fetched {
+            sleep(jittered(self.interval.as_secs_f64(), self.backoff_policy.load().jitter)).await;
+        }
+
+        let result = self.gateway.get_features_if_modified().await;
+        self.fetched = result.is_ok();
+        result
+    }
+}
+
+pub struct ServerSentEventsSource {
+    gateway: GrowthbookGateway,
+    interval: Duration,
+    response: Option<Response>,
+    parser: ServerSentEventParser,
+    events: VecDeque<ServerSentEvent>,
+    failures: u32,
+    last_refresh: Option<Instant>,
+    backoff_policy: Arc<ArcSwap<BackoffPolicy>>,
+}
+
+impl ServerSentEventsSource {
+    pub fn new(
+        api_url: &str,
+        sdk_key: &str,
+        decryption_key: Option<&str>,
+        update_interval: Duration,
+        http_timeout: Duration,
+    ) -> Result<Self, GrowthbookError> {
+        let gateway = GrowthbookGateway::new(api_url, sdk_key, decryption_key, http_timeout)?;
+        Ok(Self::from_gateway(gateway, update_interval))
+    }
+
+    pub(crate) fn from_gateway(
+        gateway: GrowthbookGateway,
+        interval: Duration,
+    ) -> Self {
+        ServerSentEventsSource {
+            gateway,
+            interval,
+            response: None,
+            parser: ServerSentEventParser::default(),
+            events: VecDeque::new(),
+            failures: 0,
+            last_refresh: None,
+            backoff_policy: Arc::new(ArcSwap::from_pointee(BackoffPolicy::default())),
+        }
+    }
+
+    pub fn with_backoff_policy(
+        self,
+        backoff_policy: BackoffPolicy,
+    ) -> Self {
+        self.with_shared_backoff_policy(Arc::new(ArcSwap::from_pointee(backoff_policy)))
+    }
+
+    pub(crate) fn with_shared_backoff_policy(
+        self,
+        backoff_policy: Arc<ArcSwap<BackoffPolicy>>,
+    ) -> Self {
+        ServerSentEventsSource { backoff_policy, ..self }
+    }
+
+    async fn refresh(&mut self) -> Result<Option<GrowthBookResponse>, GrowthbookError> {
+        let result = self.gateway.get_features_if_modified().await;
+        if result.is_ok() {
+            self.last_refresh = Some(Instant::now());
+        }
+        result
+    }
+
+    async fn reconnect_later(&mut self) {
+        self.response = None;
+        self.failures = self.failures.saturating_add(1);
+        sleep(backoff_delay(&self.backoff_policy.load(), self.failures, self.interval).min(self.interval)).await;
+    }
+}
+
+#[async_trait]
+impl FeatureSource for ServerSentEventsSource {
+    async fn next(&mut self) -> Result<Option<GrowthBookResponse>, GrowthbookError> {
+        loop {
+            let Some(last_refresh) = self.last_refresh else {
+                return self.refresh().await;
+            };
+
+            if let Some(event) = self.events.pop_front() {
+                match event.event.as_str() {
+                    "features" => match self.gateway.read_streamed_features(&event.data).await {
+                        Ok(response) => {
+                            self.last_refresh = Some(Instant::now());
+                            return Ok(Some(response));
+                        },
+                        Err(e) => {
+                            error!("[growthbook-sdk] Skipping malformed streamed features: {:?}", e);
+                            continue;
+                        },
+                    },
+                    "features-updated" => return self.refresh().await,
+                    _ => continue,
+                }
+            }
+
+            match self.response.as_mut() {
+                Some(response) => match timeout(self.interval * STREAM_IDLE_INTERVALS, response.chunk()).await {
+                    Ok(Ok(Some(chunk))) => self.events.extend(self.parser.push(&chunk)),
+                    Ok(Ok(None)) => self.reconnect_later().await,
+                    Ok(Err(e)) => {
+                        error!("[growthbook-sdk] Streaming connection lost: {:?}", e);
+                        self.reconnect_later().await;
+                    },
+                    Err(_) => {
+                        warn!("[growthbook-sdk] Streaming connection idle for too long, reconnecting");
+                        self.response = None;
+                    },
+                },
+                None if last_refresh.elapsed() >= self.interval => return self.refresh().await,
+                None => match self.gateway.subscribe().await {
+                    Ok(response) => {
+                        self.response = Some(response);
+                        self.parser = ServerSentEventParser::default();
+                        self.failures = 0;
+                    },
+                    Err(e) => {
+                        error!("[growthbook-sdk] Failed to subscribe to streaming updates, falling back to polling: {:?}", e);
+                        self.reconnect_later().await;
+                    },
+                },
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) struct FileVersion {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+impl FileVersion {
+    pub(crate) fn read(path: &Path) -> Result<Self, GrowthbookError> {
+        let metadata = std::fs::metadata(path)?;
+        Ok(FileVersion {
+            modified: metadata.modified().ok(),
+            len: metadata.len(),
+        })
+    }
+}
+
+pub struct FileSource {
+    path: PathBuf,
+    interval: Duration,
+    version: Option<FileVersion>,
+}
+
+impl FileSource {
+    pub fn new(
+        path: impl AsRef<Path>,
+        watch_interval: Duration,
+    ) -> Self {
+        FileSource {
+            path: path.as_ref().to_path_buf(),
+            interval: watch_interval,
+            version: None,
+        }
+    }
+
+    pub(crate) fn loaded(
+        path: PathBuf,
+        interval: Duration,
+        version: FileVersion,
+    ) -> Self {
+        FileSource {
+            path,
+            interval,
+            version: Some(version),
+        }
+    }
+}
+
+#[async_trait]
+impl FeatureSource for FileSource {
+    async fn next(&mut self) -> Result<Option<GrowthBookResponse>, GrowthbookError> {
+        if let Some(version) = self.version {
+            loop {
+                sleep(self.interval).await;
+                if FileVersion::read(&self.path).map_or(true, |current| current != version) {
+                    break;
+                }
+            }
+        }
+
+        self.version = None;
+        let version = FileVersion::read(&self.path)?;
+        let payload = tokio::fs::read_to_string(&self.path).await?;
+        let response = FeaturesPayloadParser::parse(&payload, None)?;
+        self.version = Some(version);
+        Ok(Some(response))
+    }
+}
+
+pub struct StaticSource {
+    response: Option<GrowthBookResponse>,
+}
+
+impl StaticSource {
+    pub fn new(payload: impl Into<FeaturesPayload>) -> Result<Self, GrowthbookError> {
+        let response = FeaturesPayloadParser::parse_payload(payload.into(), None)?;
+        Ok(StaticSource::from(response))
+    }
+}
+
+impl From<GrowthBookResponse> for StaticSource {
+    fn from(response: GrowthBookResponse) -> Self {
+        StaticSource { response: Some(response) }
+    }
+}
+
+#[async_trait]
+impl FeatureSource for StaticSource {
+    async fn next(&mut self) -> Result<Option<GrowthBookResponse>, GrowthbookError> {
+        match self.response.take() {
+            Some(response) => Ok(Some(response)),
+            None => std::future::pending().await,
+        }
+    }
+}
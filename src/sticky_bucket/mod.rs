@@ -0,0 +1,3 @@
+pub mod model;
+pub mod service;
+pub mod use_case;
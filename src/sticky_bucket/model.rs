@@ -0,0 +1,36 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct StickyAssignmentsDocument {
+    pub attribute_name: String,
+    pub attribute_value: String,
+    pub assignments: HashMap<String, String>,
+}
+
+impl StickyAssignmentsDocument {
+    pub fn new(
+        attribute_name: &str,
+        attribute_value: &str,
+        assignments: HashMap<String, String>,
+    ) -> Self {
+        StickyAssignmentsDocument {
+            attribute_name: String::from(attribute_name),
+            attribute_value: String::from(attribute_value),
+            assignments,
+        }
+    }
+
+    pub fn key(&self) -> String {
+        Self::key_of(&self.attribute_name, &self.attribute_value)
+    }
+
+    pub fn key_of(
+        attribute_name: &str,
+        attribute_value: &str,
+    ) -> String {
+        format!("{attribute_name}||{attribute_value}")
+    }
+}
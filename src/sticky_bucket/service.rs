@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use tracing::error;
+
+use crate::sticky_bucket::model::StickyAssignmentsDocument;
+
+pub trait StickyBucketService: Send + Sync {
+    fn get_assignments(
+        &self,
+        attribute_name: &str,
+        attribute_value: &str,
+    ) -> Option<StickyAssignmentsDocument>;
+
+    fn save_assignments(
+        &self,
+        document: StickyAssignmentsDocument,
+    );
+}
+
+#[derive(Default)]
+pub struct InMemoryStickyBucketService {
+    documents: RwLock<HashMap<String, StickyAssignmentsDocument>>,
+}
+
+impl InMemoryStickyBucketService {
+    pub fn all_assignments(&self) -> HashMap<String, StickyAssignmentsDocument> {
+        match self.documents.read() {
+            Ok(documents) => documents.clone(),
+            Err(e) => {
+                error!("[growthbook-sdk] problem to reading sticky bucket data returning empty {:?}", e);
+                HashMap::new()
+            },
+        }
+    }
+}
+
+impl StickyBucketService for InMemoryStickyBucketService {
+    fn get_assignments(
+        &self,
+        attribute_name: &str,
+        attribute_value: &str,
+    ) -> Option<StickyAssignmentsDocument> {
+        match self.documents.read() {
+            Ok(documents) => documents.get(&StickyAssignmentsDocument::key_of(attribute_name, attribute_value)).cloned(),
+            Err(e) => {
+                error!("[growthbook-sdk] problem to reading sticky bucket data {:?}", e);
+                None
+            },
+        }
+    }
+
+    fn save_assignments(
+        &self,
+        document: StickyAssignmentsDocument,
+    ) {
+        match self.documents.write() {
+            Ok(mut documents) => {
+                documents.insert(document.key(), document);
+            },
+            Err(e) => {
+                error!("[growthbook-sdk] problem to writing sticky bucket data {:?}", e);
+            },
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use crate::extensions::{FindGrowthBookAttribute, JsonHelper};
+use crate::model_public::{GrowthBookAttribute, GrowthBookAttributeValue};
+use crate::sticky_bucket::model::StickyAssignmentsDocument;
+use crate::sticky_bucket::service::StickyBucketService;
+
+pub struct StickyBucketVariation {
+    pub variation: Option<usize>,
+    pub version_is_blocked: bool,
+}
+
+pub struct StickyBucket;
+
+impl StickyBucket {
+    #[allow(clippy::too_many_arguments)]
+    pub fn get_variation(
+        service: &dyn StickyBucketService,
+        experiment_key: &str,
+        bucket_version: Option<i64>,
+        min_bucket_version: Option<i64>,
+        meta: &Option<Value>,
+        hash_attribute: &str,
+        fallback_attribute: Option<&str>,
+        user_attributes: &[GrowthBookAttribute],
+    ) -> StickyBucketVariation {
+        let assignments = get_assignments(service, hash_attribute, fallback_attribute, user_attributes);
+
+        let min_bucket_version = min_bucket_version.unwrap_or(0);
+        if (0..min_bucket_version).any(|version| assignments.contains_key(&Self::experiment_key(experiment_key, Some(version)))) {
+            return StickyBucketVariation {
+                variation: None,
+                version_is_blocked: true,
+            };
+        }
+
+        let variation = assignments.get(&Self::experiment_key(experiment_key, bucket_version)).and_then(|variation_key| {
+            meta.as_ref()
+                .and_then(|it| it.as_array())
+                .and_then(|meta| meta.iter().position(|item| item.get("key").map(|key| &key.force_string("") == variation_key).unwrap_or(false)))
+        });
+
+        StickyBucketVariation { variation, version_is_blocked: false }
+    }
+
+    pub fn save_assignment(
+        service: &dyn StickyBucketService,
+        attribute_name: &str,
+        attribute_value: &str,
+        experiment_key: &str,
+        bucket_version: Option<i64>,
+        variation_key: &str,
+    ) {
+        let key = Self::experiment_key(experiment_key, bucket_version);
+        let mut assignments = service.get_assignments(attribute_name, attribute_value).map(|document| document.assignments).unwrap_or_default();
+        if assignments.get(&key).map(|it| it.as_str()) != Some(variation_key) {
+            assignments.insert(key, String::from(variation_key));
+            service.save_assignments(StickyAssignmentsDocument::new(attribute_name, attribute_value, assignments));
+        }
+    }
+
+    pub fn experiment_key(
+        experiment_key: &str,
+        bucket_version: Option<i64>,
+    ) -> String {
+        format!("{experiment_key}__{}", bucket_version.unwrap_or(0))
+    }
+}
+
+fn get_assignments(
+    service: &dyn StickyBucketService,
+    hash_attribute: &str,
+    fallback_attribute: Option<&str>,
+    user_attributes: &[GrowthBookAttribute],
+) -> HashMap<String, String> {
+    let mut assignments = HashMap::new();
+
+    let attribute_names = fallback_attribute.into_iter().chain(std::iter::once(hash_attribute));
+    for attribute_name in attribute_names {
+        if let Some(attribute_value) = find_hash_value(attribute_name, user_attributes) {
+            if let Some(document) = service.get_assignments(attribute_name, &attribute_value) {
+                assignments.extend(document.assignments);
+            }
+        }
+    }
+
+    assignments
+}
+
+fn find_hash_value(
+    attribute_name: &str,
+    user_attributes: &[GrowthBookAttribute],
+) -> Option<String> {
+    match user_attributes.find_value(attribute_name) {
+        None | Some(GrowthBookAttributeValue::Empty) | Some(GrowthBookAttributeValue::Null) => None,
+        Some(value) => Some(value.to_string()).filter(|it| !it.is_empty()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+    use std::fs;
+    use std::sync::Arc;
+
+    use serde::Deserialize;
+    use serde_json::Value;
+
+    use crate::dto::GrowthBookFeature;
+    use crate::growthbook::GrowthBook;
+    use crate::model_public::GrowthBookAttribute;
+    use crate::sticky_bucket::model::StickyAssignmentsDocument;
+    use crate::sticky_bucket::service::{InMemoryStickyBucketService, StickyBucketService};
+
+    #[tokio::test]
+    async fn evaluate_sticky_bucket() -> Result<(), Box<dyn std::error::Error>> {
+        let cases = Cases::new();
+
+        for value in cases.sticky_bucket {
+            let eval_sticky_bucket = EvalStickyBucket::new(value);
+            let service = Arc::new(InMemoryStickyBucketService::default());
+            for document in eval_sticky_bucket.documents.clone() {
+                service.save_assignments(document);
+            }
+
+            let gb = GrowthBook {
+                features: Arc::new(eval_sticky_bucket.features.clone()),
+                sticky_bucket_service: Some(service.clone()),
+                ..GrowthBook::default()
+            };
+            let result = gb.check(&eval_sticky_bucket.feature_name, &Some(eval_sticky_bucket.attributes.clone()), None);
+
+            let experiment_result = result
+                .experiment_result
+                .map(|it| serde_json::from_str::<Value>(&serde_json::to_string(&it).expect("Failed to serialize experiment result")).expect("Failed to parse experiment result"));
+            assert_eq!(eval_sticky_bucket.result, experiment_result, "Invalid experiment result for '{}'", eval_sticky_bucket.name);
+
+            let all_assignments = service.all_assignments();
+            for (key, expected_document) in &eval_sticky_bucket.expected_documents {
+                assert_eq!(Some(expected_document), all_assignments.get(key), "Invalid sticky bucket document for '{}'", eval_sticky_bucket.name);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[derive(Deserialize, Clone)]
+    #[serde(rename_all = "camelCase")]
+    struct Cases {
+        sticky_bucket: Vec<Value>,
+    }
+
+    #[derive(Deserialize, Clone)]
+    #[serde(rename_all = "camelCase")]
+    struct StickyBucketContext {
+        attributes: Value,
+        features: HashMap<String, GrowthBookFeature>,
+    }
+
+    pub struct EvalStickyBucket {
+        name: String,
+        attributes: Vec<GrowthBookAttribute>,
+        features: HashMap<String, GrowthBookFeature>,
+        documents: Vec<StickyAssignmentsDocument>,
+        feature_name: String,
+        result: Option<Value>,
+        expected_documents: HashMap<String, StickyAssignmentsDocument>,
+    }
+
+    impl EvalStickyBucket {
+        fn new(value: Value) -> Self {
+            let array = value.as_array().expect("Failed to convert to array");
+            let context = serde_json::from_value::<StickyBucketContext>(array[1].clone()).expect("Failed to convert to StickyBucketContext");
+            Self {
+                name: array[0].as_str().expect("Failed to convert to str").to_string(),
+                attributes: GrowthBookAttribute::from(context.attributes).expect("Failed to convert to GrowthBookAttribute"),
+                features: context.features,
+                documents: serde_json::from_value(array[2].clone()).expect("Failed to convert to StickyAssignmentsDocument"),
+                feature_name: array[3].as_str().expect("Failed to convert to str").to_string(),
+                result: Some(array[4].clone()).filter(|it| !it.is_null()),
+                expected_documents: serde_json::from_value(array[5].clone()).expect("Failed to convert to StickyAssignmentsDocument"),
+            }
+        }
+    }
+
+    impl Cases {
+        pub fn new() -> Self {
+            let contents = fs::read_to_string("./tests/all_cases.json").expect("Should have been able to read the file");
+
+            serde_json::from_str(&contents).expect("Failed to create cases")
+        }
+    }
+}
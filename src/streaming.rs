@@ -0,0 +1,64 @@
+#[derive(Clone, Debug, PartialEq)]
+pub struct ServerSentEvent {
+    pub event: String,
+    pub data: String,
+}
+
+#[derive(Default)]
+pub struct ServerSentEventParser {
+    buffer: Vec<u8>,
+    event: Option<String>,
+    data: Vec<String>,
+}
+
+impl ServerSentEventParser {
+    pub fn push(
+        &mut self,
+        chunk: &[u8],
+    ) -> Vec<ServerSentEvent> {
+        self.buffer.extend_from_slice(chunk);
+
+        let mut events = vec![];
+        while let Some(position) = self.buffer.iter().position(|it| *it == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=position).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches('\n').trim_end_matches('\r');
+            if let Some(event) = self.process_line(line) {
+                events.push(event);
+            }
+        }
+        events
+    }
+
+    fn process_line(
+        &mut self,
+        line: &str,
+    ) -> Option<ServerSentEvent> {
+        if line.is_empty() {
+            return self.dispatch();
+        }
+        if line.starts_with(':') {
+            return None;
+        }
+
+        let (field, value) = line.split_once(':').unwrap_or((line, ""));
+        let value = value.strip_prefix(' ').unwrap_or(value);
+        match field {
+            "event" => self.event = Some(String::from(value)),
+            "data" => self.data.push(String::from(value)),
+            _ => {},
+        }
+        None
+    }
+
+    fn dispatch(&mut self) -> Option<ServerSentEvent> {
+        let event = self.event.take().unwrap_or(String::from("message"));
+        if self.data.is_empty() {
+            return None;
+        }
+
+        let data = self.data.join("\n");
+        self.data.clear();
+        Some(ServerSentEvent { event, data })
+    }
+}
@@ -0,0 +1,198 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+
+use tracing::error;
+
+use crate::extensions::JsonHelper;
+use crate::model_private::FeatureResult;
+use crate::model_public::{Experiment, ExperimentResult};
+
+pub trait TrackingCallback: Send + Sync {
+    fn on_experiment_viewed(
+        &self,
+        experiment: &Experiment,
+        experiment_result: &ExperimentResult,
+    );
+}
+
+impl<F> TrackingCallback for F
+where
+    F: Fn(&Experiment, &ExperimentResult) + Send + Sync,
+{
+    fn on_experiment_viewed(
+        &self,
+        experiment: &Experiment,
+        experiment_result: &ExperimentResult,
+    ) {
+        self(experiment, experiment_result)
+    }
+}
+
+const TRACKED_EXPOSURES_CAPACITY: usize = 10_000;
+const TRACKED_FEATURES_CAPACITY: usize = 10_000;
+
+type ExposureKey = (String, String, String, i64);
+
+// least recently used keys are evicted once the capacity is reached
+struct RecentKeys<K> {
+    capacity: usize,
+    generation: u64,
+    keys: HashMap<K, u64>,
+    order: BTreeMap<u64, K>,
+}
+
+impl<K: Eq + Hash + Clone> RecentKeys<K> {
+    fn new(capacity: usize) -> Self {
+        RecentKeys {
+            capacity,
+            generation: 0,
+            keys: HashMap::new(),
+            order: BTreeMap::new(),
+        }
+    }
+
+    fn insert(
+        &mut self,
+        key: K,
+    ) -> bool {
+        self.generation += 1;
+        if let Some(generation) = self.keys.insert(key.clone(), self.generation) {
+            self.order.remove(&generation);
+            self.order.insert(self.generation, key);
+            return false;
+        }
+
+        self.order.insert(self.generation, key);
+        if self.keys.len() > self.capacity {
+            if let Some((_, oldest)) = self.order.pop_first() {
+                self.keys.remove(&oldest);
+            }
+        }
+        true
+    }
+}
+
+pub struct ExperimentTracker {
+    callback: Arc<dyn TrackingCallback>,
+    tracked: Mutex<RecentKeys<ExposureKey>>,
+}
+
+impl ExperimentTracker {
+    pub fn new(callback: Arc<dyn TrackingCallback>) -> Self {
+        ExperimentTracker {
+            callback,
+            tracked: Mutex::new(RecentKeys::new(TRACKED_EXPOSURES_CAPACITY)),
+        }
+    }
+
+    pub fn track(
+        &self,
+        experiment: &Experiment,
+        experiment_result: &ExperimentResult,
+    ) {
+        if !experiment_result.in_experiment || !experiment_result.hash_used {
+            return;
+        }
+
+        let key = (
+            experiment_result.hash_attribute.clone().unwrap_or_default(),
+            experiment_result.hash_value.as_ref().map(|it| it.force_string("")).unwrap_or_default(),
+            experiment.key.clone(),
+            experiment_result.variation_id,
+        );
+
+        let first_exposure = match self.tracked.lock() {
+            Ok(mut tracked) => tracked.insert(key),
+            Err(e) => {
+                error!("[growthbook-sdk] problem to reading tracked experiments {:?}", e);
+                false
+            },
+        };
+
+        if first_exposure {
+            self.callback.on_experiment_viewed(experiment, experiment_result);
+        }
+    }
+}
+
+pub trait FeatureUsageCallback: Send + Sync {
+    fn on_feature_usage(
+        &self,
+        feature_key: &str,
+        feature_result: &FeatureResult,
+    );
+}
+
+impl<F> FeatureUsageCallback for F
+where
+    F: Fn(&str, &FeatureResult) + Send + Sync,
+{
+    fn on_feature_usage(
+        &self,
+        feature_key: &str,
+        feature_result: &FeatureResult,
+    ) {
+        self(feature_key, feature_result)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FeatureUsageOptions {
+    pub sampling_rate: f32,
+    pub deduplicate: bool,
+}
+
+impl Default for FeatureUsageOptions {
+    fn default() -> Self {
+        FeatureUsageOptions {
+            sampling_rate: 1.0,
+            deduplicate: false,
+        }
+    }
+}
+
+pub struct FeatureUsageTracker {
+    callback: Arc<dyn FeatureUsageCallback>,
+    options: FeatureUsageOptions,
+    tracked: Mutex<RecentKeys<(String, String)>>,
+}
+
+impl FeatureUsageTracker {
+    pub fn new(
+        callback: Arc<dyn FeatureUsageCallback>,
+        options: FeatureUsageOptions,
+    ) -> Self {
+        FeatureUsageTracker {
+            callback,
+            options,
+            tracked: Mutex::new(RecentKeys::new(TRACKED_FEATURES_CAPACITY)),
+        }
+    }
+
+    pub fn track(
+        &self,
+        feature_key: &str,
+        feature_result: &FeatureResult,
+    ) {
+        if self.options.sampling_rate < 1.0 && rand::random::<f32>() >= self.options.sampling_rate {
+            return;
+        }
+
+        if self.options.deduplicate {
+            let key = (String::from(feature_key), feature_result.value.to_string());
+            let already_tracked = match self.tracked.lock() {
+                Ok(mut tracked) => !tracked.insert(key),
+                Err(e) => {
+                    error!("[growthbook-sdk] problem to reading tracked features {:?}", e);
+                    true
+                },
+            };
+            if already_tracked {
+                return;
+            }
+        }
+
+        self.callback.on_feature_usage(feature_key, feature_result);
+    }
+}
@@ -0,0 +1 @@
+pub mod use_case;
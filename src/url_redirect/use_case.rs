@@ -0,0 +1,273 @@
+use lazy_static::lazy_static;
+use regex::{Regex, RegexBuilder};
+use url::{Position, Url};
+
+use crate::model_public::UrlPattern;
+
+lazy_static! {
+    static ref HOST_WITHOUT_SCHEME: Regex = Regex::new(r"^([^:/?]*)\.").expect("Failed to compile host without scheme regex");
+}
+
+pub struct UrlTargeting;
+
+impl UrlTargeting {
+    pub fn is_targeted(
+        url: &str,
+        url_patterns: &[UrlPattern],
+    ) -> bool {
+        if url_patterns.is_empty() {
+            return false;
+        }
+        let Some(actual) = parse(url, "https://_") else {
+            return false;
+        };
+
+        let mut has_include_rules = false;
+        let mut is_included = false;
+        for url_pattern in url_patterns {
+            let matches = url_pattern.matcher().matches(&actual);
+            if url_pattern.include == Some(false) {
+                if matches {
+                    return false;
+                }
+            } else {
+                has_include_rules = true;
+                if matches {
+                    is_included = true;
+                }
+            }
+        }
+
+        is_included || !has_include_rules
+    }
+
+    pub fn merge_query_strings(
+        old_url: &str,
+        new_url: &str,
+    ) -> String {
+        let (Ok(current), Ok(mut redirect)) = (Url::parse(old_url), Url::parse(new_url)) else {
+            return String::from(new_url);
+        };
+
+        let redirect_keys: Vec<String> = redirect.query_pairs().map(|(key, _)| key.into_owned()).collect();
+        let missing: Vec<(String, String)> = current
+            .query_pairs()
+            .filter(|(key, _)| !redirect_keys.iter().any(|it| it == key))
+            .map(|(key, value)| (key.into_owned(), value.into_owned()))
+            .collect();
+
+        if !missing.is_empty() {
+            redirect.query_pairs_mut().extend_pairs(missing);
+        }
+
+        redirect.to_string()
+    }
+}
+
+fn parse(
+    url: &str,
+    base: &str,
+) -> Option<Url> {
+    let base = Url::parse(base).ok()?;
+    Url::options().base_url(Some(&base)).parse(url).ok()
+}
+
+#[derive(Clone, Debug)]
+pub enum UrlMatcher {
+    Regex(Regex),
+    Simple(Vec<SimplePart>),
+    Invalid,
+}
+
+#[derive(Clone, Debug)]
+pub struct SimplePart {
+    target: SimpleTarget,
+    regex: Regex,
+}
+
+#[derive(Clone, Debug)]
+enum SimpleTarget {
+    Host,
+    Path,
+    Fragment,
+    Query(String),
+}
+
+impl UrlMatcher {
+    pub fn compile(url_pattern: &UrlPattern) -> Self {
+        let matcher = match url_pattern.pattern_type.as_str() {
+            "regex" => Regex::new(&url_pattern.pattern).ok().map(UrlMatcher::Regex),
+            "simple" => compile_simple(&url_pattern.pattern),
+            _ => None,
+        };
+        matcher.unwrap_or(UrlMatcher::Invalid)
+    }
+
+    fn matches(
+        &self,
+        actual: &Url,
+    ) -> bool {
+        match self {
+            UrlMatcher::Regex(regex) => regex.is_match(actual.as_str()) || regex.is_match(&actual[Position::BeforePath..]),
+            UrlMatcher::Simple(parts) => parts.iter().all(|part| part.regex.is_match(&part.target.value(actual))),
+            UrlMatcher::Invalid => false,
+        }
+    }
+}
+
+impl SimpleTarget {
+    fn value(
+        &self,
+        actual: &Url,
+    ) -> String {
+        match self {
+            SimpleTarget::Host => host(actual),
+            SimpleTarget::Path => actual.path().to_string(),
+            SimpleTarget::Fragment => actual.fragment().unwrap_or_default().to_string(),
+            SimpleTarget::Query(key) => actual
+                .query_pairs()
+                .find(|(actual_key, _)| actual_key == key.as_str())
+                .map(|(_, it)| it.into_owned())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+fn compile_simple(pattern: &str) -> Option<UrlMatcher> {
+    let pattern = HOST_WITHOUT_SCHEME.replace(pattern, "https://$1.").replace('*', "_____");
+    let expected = parse(&pattern, "https://_____")?;
+
+    let mut parts = vec![(SimpleTarget::Host, host(&expected), false), (SimpleTarget::Path, expected.path().to_string(), true)];
+    if let Some(fragment) = expected.fragment().filter(|it| !it.is_empty()) {
+        parts.push((SimpleTarget::Fragment, fragment.to_string(), false));
+    }
+    for (key, value) in expected.query_pairs() {
+        parts.push((SimpleTarget::Query(key.into_owned()), value.into_owned(), false));
+    }
+
+    parts
+        .into_iter()
+        .map(|(target, pattern, is_path)| simple_part_regex(&pattern, is_path).map(|regex| SimplePart { target, regex }))
+        .collect::<Option<Vec<_>>>()
+        .map(UrlMatcher::Simple)
+}
+
+fn simple_part_regex(
+    pattern: &str,
+    is_path: bool,
+) -> Option<Regex> {
+    let mut escaped = regex::escape(pattern).replace("_____", ".*");
+    if is_path {
+        let trimmed = escaped.strip_prefix('/').unwrap_or(&escaped);
+        let trimmed = trimmed.strip_suffix('/').unwrap_or(trimmed);
+        escaped = format!("/?{trimmed}/?");
+    }
+
+    RegexBuilder::new(&format!("^{escaped}$")).case_insensitive(true).build().ok()
+}
+
+fn host(url: &Url) -> String {
+    match (url.host_str(), url.port()) {
+        (Some(host), Some(port)) => format!("{host}:{port}"),
+        (Some(host), None) => String::from(host),
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+    use std::sync::Arc;
+
+    use serde::Deserialize;
+    use serde_json::Value;
+
+    use crate::growthbook::GrowthBook;
+    use crate::model_public::{Experiment, GrowthBookAttribute};
+
+    #[tokio::test]
+    async fn evaluate_url_redirect() -> Result<(), Box<dyn std::error::Error>> {
+        let cases = Cases::new();
+
+        for value in cases.url_redirect {
+            let eval_url_redirect = EvalUrlRedirect::new(value);
+            let gb = GrowthBook {
+                experiments: Arc::new(eval_url_redirect.experiments),
+                ..GrowthBook::default()
+            };
+
+            let redirect = gb.redirect(&eval_url_redirect.url, &Some(eval_url_redirect.attributes));
+
+            match eval_url_redirect.expected.first() {
+                Some(expected) => {
+                    let redirect = redirect.unwrap_or_else(|| panic!("Expected redirect for '{}'", eval_url_redirect.name));
+                    assert_eq!(
+                        expected.get("inExperiment").and_then(Value::as_bool),
+                        Some(redirect.experiment_result.in_experiment),
+                        "Invalid inExperiment for '{}'",
+                        eval_url_redirect.name
+                    );
+                    assert_eq!(
+                        expected.get("urlRedirect"),
+                        redirect.experiment_result.value.get("urlRedirect"),
+                        "Invalid urlRedirect for '{}'",
+                        eval_url_redirect.name
+                    );
+                    assert_eq!(
+                        expected.get("urlWithParams").and_then(Value::as_str),
+                        Some(redirect.url.as_str()),
+                        "Invalid urlWithParams for '{}'",
+                        eval_url_redirect.name
+                    );
+                },
+                None => assert!(redirect.is_none(), "Unexpected redirect for '{}'", eval_url_redirect.name),
+            }
+        }
+
+        Ok(())
+    }
+
+    #[derive(Deserialize, Clone)]
+    #[serde(rename_all = "camelCase")]
+    struct Cases {
+        url_redirect: Vec<Value>,
+    }
+
+    #[derive(Deserialize, Clone)]
+    #[serde(rename_all = "camelCase")]
+    struct UrlRedirectContext {
+        attributes: Value,
+        url: String,
+        experiments: Vec<Experiment>,
+    }
+
+    pub struct EvalUrlRedirect {
+        name: String,
+        attributes: Vec<GrowthBookAttribute>,
+        url: String,
+        experiments: Vec<Experiment>,
+        expected: Vec<Value>,
+    }
+
+    impl EvalUrlRedirect {
+        fn new(value: Value) -> Self {
+            let array = value.as_array().expect("Failed to convert to array");
+            let context = serde_json::from_value::<UrlRedirectContext>(array[1].clone()).expect("Failed to convert to UrlRedirectContext");
+            Self {
+                name: array[0].as_str().expect("Failed to convert to str").to_string(),
+                attributes: GrowthBookAttribute::from(context.attributes).expect("Failed to convert to GrowthBookAttribute"),
+                url: context.url,
+                experiments: context.experiments,
+                expected: array[2].as_array().expect("Failed to convert to array").clone(),
+            }
+        }
+    }
+
+    impl Cases {
+        pub fn new() -> Self {
+            let contents = fs::read_to_string("./tests/all_cases.json").expect("Should have been able to read the file");
+
+            serde_json::from_str(&contents).expect("Failed to create cases")
+        }
+    }
+}
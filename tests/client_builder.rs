@@ -0,0 +1,202 @@
+mod commons;
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use growthbook_rust_sdk::client::GrowthBookClient;
+    use growthbook_rust_sdk::error::{ConfigurationError, GrowthbookError, GrowthbookErrorCode};
+    use growthbook_rust_sdk::model_public::{Experiment, GrowthBookAttribute};
+    use serde_json::json;
+    use uuid::Uuid;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use crate::commons::{features_response, mock_features, ENABLED_FEATURES};
+
+    const FEATURES: &str = r#"{
+        "features": {
+            "feature": { "defaultValue": false },
+            "country_feature": { "defaultValue": false, "rules": [{ "condition": { "country": "BR" }, "force": true }] }
+        }
+    }"#;
+
+    fn configuration_error(result: Result<GrowthBookClient, GrowthbookError>) -> Option<ConfigurationError> {
+        match result.err().map(|it| it.code) {
+            Some(GrowthbookErrorCode::InvalidConfiguration(error)) => Some(error),
+            _ => None,
+        }
+    }
+
+    #[tokio::test]
+    async fn should_fail_without_api_host() -> Result<(), Box<dyn std::error::Error>> {
+        let result = GrowthBookClient::builder().client_key("sdk-key").build().await;
+
+        assert_eq!(Some(ConfigurationError::MissingApiHost), configuration_error(result));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_fail_with_invalid_api_host() -> Result<(), Box<dyn std::error::Error>> {
+        let result = GrowthBookClient::builder().api_host("ftp://growthbook.io").client_key("sdk-key").build().await;
+
+        assert_eq!(Some(ConfigurationError::InvalidApiHost(String::from("ftp://growthbook.io"))), configuration_error(result));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_fail_with_blank_client_key() -> Result<(), Box<dyn std::error::Error>> {
+        let result = GrowthBookClient::builder().api_host("http://localhost").client_key("  ").build().await;
+
+        assert_eq!(Some(ConfigurationError::MissingClientKey), configuration_error(result));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_fail_with_invalid_decryption_key() -> Result<(), Box<dyn std::error::Error>> {
+        let result = GrowthBookClient::builder().api_host("http://localhost").client_key("sdk-key").decryption_key("not-a-key").build().await;
+
+        assert_eq!(Some(ConfigurationError::InvalidDecryptionKey), configuration_error(result));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_fail_with_zero_update_interval() -> Result<(), Box<dyn std::error::Error>> {
+        let result = GrowthBookClient::builder()
+            .api_host("http://localhost")
+            .client_key("sdk-key")
+            .update_interval(Duration::ZERO)
+            .build()
+            .await;
+
+        assert_eq!(Some(ConfigurationError::InvalidUpdateInterval), configuration_error(result));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_fail_with_invalid_header() -> Result<(), Box<dyn std::error::Error>> {
+        let result = GrowthBookClient::builder()
+            .api_host("http://localhost")
+            .client_key("sdk-key")
+            .header("bad header", "value")
+            .build()
+            .await;
+
+        assert_eq!(Some(ConfigurationError::InvalidHeader(String::from("bad header"))), configuration_error(result));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_fail_with_invalid_bootstrap_payload() -> Result<(), Box<dyn std::error::Error>> {
+        let result = GrowthBookClient::builder().api_host("http://localhost").client_key("sdk-key").bootstrap("{ features").build().await;
+
+        assert!(matches!(configuration_error(result), Some(ConfigurationError::InvalidBootstrapPayload(_))));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_fail_when_cache_directory_is_missing() -> Result<(), Box<dyn std::error::Error>> {
+        let cache_path = std::env::temp_dir().join(Uuid::now_v7().to_string()).join("features.json");
+
+        let result = GrowthBookClient::builder().api_host("http://localhost").client_key("sdk-key").cache_path(&cache_path).build().await;
+
+        assert_eq!(Some(ConfigurationError::InvalidCachePath(cache_path)), configuration_error(result));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_fail_with_negative_forced_variation() -> Result<(), Box<dyn std::error::Error>> {
+        let result = GrowthBookClient::builder()
+            .api_host("http://localhost")
+            .client_key("sdk-key")
+            .forced_variation("experiment", -1)
+            .build()
+            .await;
+
+        assert_eq!(Some(ConfigurationError::InvalidForcedVariation(String::from("experiment"))), configuration_error(result));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_send_custom_headers() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        let sdk_key = Uuid::now_v7();
+        Mock::given(method("GET"))
+            .and(path(format!("/api/features/{sdk_key}")))
+            .and(header("x-tenant", "acme"))
+            .respond_with(features_response(FEATURES))
+            .mount(&mock_server)
+            .await;
+
+        let client = GrowthBookClient::builder()
+            .api_host(&format!("{}/", mock_server.uri()))
+            .client_key(&sdk_key.to_string())
+            .header("x-tenant", "acme")
+            .build()
+            .await?;
+
+        assert_eq!(2, client.total_features());
+
+        client.shutdown().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_apply_forced_features_variations_and_attributes() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        let sdk_key = Uuid::now_v7();
+        mock_features(&mock_server, sdk_key, features_response(FEATURES), None).await;
+
+        let client = GrowthBookClient::builder()
+            .api_host(&mock_server.uri())
+            .client_key(&sdk_key.to_string())
+            .forced_feature("feature", json!(true))
+            .forced_variation("builder-experiment", 1)
+            .attributes(GrowthBookAttribute::from(json!({ "country": "BR" }))?)
+            .build()
+            .await?;
+
+        let experiment = Experiment {
+            key: String::from("builder-experiment"),
+            variations: vec![json!("control"), json!("treatment")],
+            ..Experiment::default()
+        };
+        let user_attributes = GrowthBookAttribute::from(json!({ "country": "US" }))?;
+
+        assert!(client.is_on("feature", None));
+        assert!(client.is_on("country_feature", None));
+        assert!(client.is_off("country_feature", Some(user_attributes)));
+        assert_eq!(json!("treatment"), client.run(&experiment, None).value);
+
+        client.shutdown().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_fall_back_to_bootstrap_payload() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        let sdk_key = Uuid::now_v7();
+        mock_features(&mock_server, sdk_key, ResponseTemplate::new(500), None).await;
+
+        let client = GrowthBookClient::builder()
+            .api_host(&mock_server.uri())
+            .client_key(&sdk_key.to_string())
+            .bootstrap(ENABLED_FEATURES)
+            .build()
+            .await?;
+
+        assert!(client.is_on("feature", None));
+
+        client.shutdown().await;
+        Ok(())
+    }
+}
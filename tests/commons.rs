@@ -1,6 +1,8 @@
 use std::net::{SocketAddr, TcpListener};
+use std::time::Duration;
 
 use growthbook_rust_sdk::client::GrowthBookClient;
+use growthbook_rust_sdk::error::GrowthbookError;
 use rand::Rng;
 use reqwest::StatusCode;
 use serde_json::{json, Value};
@@ -9,6 +11,53 @@ use uuid::Uuid;
 use wiremock::matchers::{method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
+#[allow(dead_code)]
+pub const ENABLED_FEATURES: &str = r#"{ "features": { "feature": { "defaultValue": true } } }"#;
+#[allow(dead_code)]
+pub const DISABLED_FEATURES: &str = r#"{ "features": { "feature": { "defaultValue": false } } }"#;
+
+#[allow(dead_code)]
+pub fn features_response(features: &str) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_raw(features, "application/json")
+}
+
+#[allow(dead_code)]
+pub async fn mock_features(
+    mock_server: &MockServer,
+    sdk_key: Uuid,
+    response: ResponseTemplate,
+    times: Option<u64>,
+) {
+    let mock = Mock::given(method("GET")).and(path(format!("/api/features/{sdk_key}"))).respond_with(response);
+    match times {
+        Some(times) => mock.up_to_n_times(times).mount(mock_server).await,
+        None => mock.mount(mock_server).await,
+    }
+}
+
+#[allow(dead_code)]
+pub async fn mock_stream(
+    mock_server: &MockServer,
+    sdk_key: Uuid,
+    response: ResponseTemplate,
+) {
+    Mock::given(method("GET")).and(path(format!("/sub/{sdk_key}"))).respond_with(response).mount(mock_server).await;
+}
+
+#[allow(dead_code)]
+pub async fn create_client(
+    mock_server: &MockServer,
+    sdk_key: Uuid,
+    update_interval: Option<Duration>,
+) -> Result<GrowthBookClient, GrowthbookError> {
+    GrowthBookClient::new(&mock_server.uri(), sdk_key.to_string().as_str(), update_interval, None).await
+}
+
+#[allow(dead_code)]
+pub async fn requests(mock_server: &MockServer) -> usize {
+    mock_server.received_requests().await.unwrap_or_default().len()
+}
+
 #[allow(dead_code)]
 pub struct TestContext {
     pub mock_server: MockServer,
@@ -30,6 +79,7 @@ impl AsyncTestContext for TestContext {
     }
 }
 
+#[allow(dead_code)]
 pub async fn all_cases(
     mock_server: &MockServer,
     sdk: Uuid,
@@ -142,6 +192,84 @@ pub async fn all_cases(
                     }
                 ]
             },
+            "in-group-rule": {
+                "defaultValue": false,
+                "rules": [
+                    {
+                        "condition": {
+                            "any-id": {
+                                "$inGroup": "beta-testers"
+                            }
+                        },
+                        "force": true
+                    }
+                ]
+            },
+            "not-in-group-rule": {
+                "defaultValue": false,
+                "rules": [
+                    {
+                        "condition": {
+                            "any-id": {
+                                "$notInGroup": "beta-testers"
+                            }
+                        },
+                        "force": true
+                    }
+                ]
+            },
+            "regexi-rule": {
+                "defaultValue": false,
+                "rules": [
+                    {
+                        "condition": {
+                            "email": {
+                                "$regexi": "@growthbook\\.io$"
+                            }
+                        },
+                        "force": true
+                    }
+                ]
+            },
+            "ini-rule": {
+                "defaultValue": false,
+                "rules": [
+                    {
+                        "condition": {
+                            "email": {
+                                "$ini": ["Alice@GrowthBook.io", "bob@growthbook.io"]
+                            }
+                        },
+                        "force": true
+                    }
+                ]
+            },
+            "nini-rule": {
+                "defaultValue": false,
+                "rules": [
+                    {
+                        "condition": {
+                            "email": {
+                                "$nini": ["Alice@GrowthBook.io", "bob@growthbook.io"]
+                            }
+                        },
+                        "force": true
+                    }
+                ]
+            },
+            "alli-rule": {
+                "defaultValue": false,
+                "rules": [
+                    {
+                        "condition": {
+                            "headers": {
+                                "$alli": ["X-Beta", "x-internal"]
+                            }
+                        },
+                        "force": true
+                    }
+                ]
+            },
             "regex-rule": {
                 "defaultValue": false,
                 "rules": [
@@ -257,6 +385,87 @@ pub async fn all_cases(
                     }
                 ]
             },
+            "experiment-rule-targeted-flag": {
+                "defaultValue": false,
+                "rules": [
+                    {
+                        "condition": {
+                            "country": "US"
+                        },
+                        "coverage": 1.0,
+                        "hashAttribute": "any-id",
+                        "variations": [
+                            true,
+                            true
+                        ],
+                        "key": "any-targeted-experiment-key"
+                    }
+                ]
+            },
+            "experiment-rule-parent-met-flag": {
+                "defaultValue": false,
+                "rules": [
+                    {
+                        "parentConditions": [
+                            {
+                                "id": "simple-flag",
+                                "condition": {
+                                    "value": true
+                                },
+                                "gate": true
+                            }
+                        ],
+                        "coverage": 1.0,
+                        "hashAttribute": "any-id",
+                        "variations": [
+                            true,
+                            true
+                        ],
+                        "key": "any-parent-experiment-key"
+                    }
+                ]
+            },
+            "experiment-rule-parent-not-met-flag": {
+                "defaultValue": true,
+                "rules": [
+                    {
+                        "parentConditions": [
+                            {
+                                "id": "simple-flag-disabled",
+                                "condition": {
+                                    "value": true
+                                },
+                                "gate": true
+                            }
+                        ],
+                        "coverage": 1.0,
+                        "hashAttribute": "any-id",
+                        "variations": [
+                            true,
+                            true
+                        ],
+                        "key": "any-parent-experiment-key"
+                    }
+                ]
+            },
+            "experiment-prerequisite-flag": {
+                "defaultValue": true,
+                "rules": [
+                    {
+                        "parentConditions": [
+                            {
+                                "id": "experiment-rule-condition-flag",
+                                "condition": {
+                                    "value": {
+                                        "$exists": true
+                                    }
+                                },
+                                "gate": true
+                            }
+                        ]
+                    }
+                ]
+            },
             "simple-flag": {
                 "defaultValue": true
             },
@@ -305,6 +514,38 @@ pub async fn all_cases(
                 ]
             }
         },
+        "experiments": [
+            {
+                "key": "checkout-redirect",
+                "hashAttribute": "any-id",
+                "urlPatterns": [
+                    {
+                        "type": "simple",
+                        "include": true,
+                        "pattern": "https://www.example.com/checkout"
+                    },
+                    {
+                        "type": "regex",
+                        "include": false,
+                        "pattern": "internal=true"
+                    }
+                ],
+                "weights": [0.0, 1.0],
+                "variations": [
+                    {},
+                    {
+                        "urlRedirect": "https://www.example.com/checkout-new"
+                    }
+                ],
+                "persistQueryString": true
+            }
+        ],
+        "savedGroups": {
+            "beta-testers": [
+                "018fcf11-bb67-7789-8d10-fcbb7de4ff7b",
+                "01901d5c-fb74-743d-a532-ed582d29a7e1"
+            ]
+        },
         "dateUpdated": "2024-05-29T18:43:22.153Z"
     });
     mock(mock_server, sdk, body, 1, StatusCode::OK).await;
@@ -331,6 +572,7 @@ async fn mock(
         .await;
 }
 
+#[allow(dead_code)]
 pub async fn create_mock_server() -> MockServer {
     for _ in 1..10 {
         // try to start mock server in a random port 10 times
@@ -0,0 +1,61 @@
+mod commons;
+
+#[cfg(test)]
+mod test {
+    use std::io::Write;
+    use std::time::Duration;
+
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use uuid::Uuid;
+    use wiremock::matchers::{header, header_regex, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use crate::commons::{create_client, features_response, mock_features, DISABLED_FEATURES, ENABLED_FEATURES};
+
+    #[tokio::test]
+    async fn should_keep_features_when_server_answers_not_modified() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        let sdk_key = Uuid::now_v7();
+        Mock::given(method("GET"))
+            .and(path(format!("/api/features/{sdk_key}")))
+            .and(header("if-none-match", "\"v1\""))
+            .respond_with(ResponseTemplate::new(304))
+            .mount(&mock_server)
+            .await;
+        mock_features(&mock_server, sdk_key, features_response(DISABLED_FEATURES).insert_header("etag", "\"v1\""), Some(1)).await;
+        mock_features(&mock_server, sdk_key, features_response(ENABLED_FEATURES), None).await;
+
+        let client = create_client(&mock_server, sdk_key, Some(Duration::from_millis(200))).await?;
+
+        tokio::time::sleep(Duration::from_secs(1)).await;
+
+        assert!(!client.is_on("feature", None));
+        assert_eq!(1, client.total_features());
+        let requests = mock_server.received_requests().await.unwrap_or_default();
+        assert!(requests.iter().filter(|it| it.headers.get("if-none-match").is_some()).count() > 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_accept_gzip_compressed_features() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        let sdk_key = Uuid::now_v7();
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(ENABLED_FEATURES.as_bytes())?;
+        Mock::given(method("GET"))
+            .and(path(format!("/api/features/{sdk_key}")))
+            .and(header_regex("accept-encoding", "gzip"))
+            .and(header_regex("accept-encoding", "br"))
+            .respond_with(ResponseTemplate::new(200).insert_header("content-encoding", "gzip").set_body_raw(encoder.finish()?, "application/json"))
+            .mount(&mock_server)
+            .await;
+
+        let client = create_client(&mock_server, sdk_key, None).await?;
+
+        assert!(client.is_on("feature", None));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+#[cfg(test)]
+mod test {
+    use growthbook_rust_sdk::client::GrowthBookClient;
+    use serde_json::json;
+    use uuid::Uuid;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const ENCRYPTED_FEATURES: &str = "m5ylFM6ndyOJA2OPadubkw==.Uu7ViqgKEt/dWvCyhI46q088PkAEJbnXKf3KPZjf9IEQQ+A8fojNoxw4wIbPX3aj";
+    const DECRYPTION_KEY: &str = "Zvwv/+uhpFDznZ6SX28Yjg==";
+
+    async fn mock_encrypted_features(sdk_key: Uuid) -> MockServer {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(format!("/api/features/{sdk_key}")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "status": 200,
+                "features": {},
+                "encryptedFeatures": ENCRYPTED_FEATURES,
+            })))
+            .mount(&mock_server)
+            .await;
+        mock_server
+    }
+
+    #[tokio::test]
+    async fn should_decrypt_features_with_decryption_key() -> Result<(), Box<dyn std::error::Error>> {
+        let sdk_key = Uuid::now_v7();
+        let mock_server = mock_encrypted_features(sdk_key).await;
+
+        let client = GrowthBookClient::new_with_decryption_key(&mock_server.uri(), sdk_key.to_string().as_str(), DECRYPTION_KEY, None, None).await?;
+
+        assert_eq!(1, client.total_features());
+        assert!(client.is_on("feature", None));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_fail_when_decryption_key_is_wrong() -> Result<(), Box<dyn std::error::Error>> {
+        let sdk_key = Uuid::now_v7();
+        let mock_server = mock_encrypted_features(sdk_key).await;
+
+        let result = GrowthBookClient::new_with_decryption_key(&mock_server.uri(), sdk_key.to_string().as_str(), "Zvwv/+uhpFDznZ6SX39Yjg==", None, None).await;
+
+        assert!(result.is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_fail_when_features_are_encrypted_and_decryption_key_is_missing() -> Result<(), Box<dyn std::error::Error>> {
+        let sdk_key = Uuid::now_v7();
+        let mock_server = mock_encrypted_features(sdk_key).await;
+
+        let result = GrowthBookClient::new(&mock_server.uri(), sdk_key.to_string().as_str(), None, None).await;
+
+        assert!(result.is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,131 @@
+mod commons;
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use async_trait::async_trait;
+    use growthbook_rust_sdk::client::GrowthBookClient;
+    use growthbook_rust_sdk::dto::GrowthBookResponse;
+    use growthbook_rust_sdk::error::{GrowthbookError, GrowthbookErrorCode};
+    use growthbook_rust_sdk::model_public::BackoffPolicy;
+    use growthbook_rust_sdk::source::{FeatureSource, FileSource, HttpPollingSource, StaticSource};
+    use tokio::sync::mpsc::{channel, Receiver};
+    use uuid::Uuid;
+    use wiremock::MockServer;
+
+    use crate::commons::{features_response, mock_features, DISABLED_FEATURES, ENABLED_FEATURES};
+
+    struct ChannelSource {
+        receiver: Receiver<Result<&'static str, GrowthbookError>>,
+    }
+
+    #[async_trait]
+    impl FeatureSource for ChannelSource {
+        async fn next(&mut self) -> Result<Option<GrowthBookResponse>, GrowthbookError> {
+            match self.receiver.recv().await {
+                Some(payload) => Ok(Some(serde_json::from_str(payload?)?)),
+                None => std::future::pending().await,
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn should_apply_snapshots_from_custom_source() -> Result<(), Box<dyn std::error::Error>> {
+        let (sender, receiver) = channel(1);
+        sender.send(Ok(DISABLED_FEATURES)).await?;
+
+        let client = GrowthBookClient::from_source(ChannelSource { receiver }, None).await?;
+        assert!(!client.is_on("feature", None));
+
+        sender.send(Ok(ENABLED_FEATURES)).await?;
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        assert!(client.is_on("feature", None));
+        assert!(client.is_ready());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_stop_when_custom_source_reports_invalid_sdk_key() -> Result<(), Box<dyn std::error::Error>> {
+        let (sender, receiver) = channel(1);
+        sender.send(Ok(ENABLED_FEATURES)).await?;
+
+        let client = GrowthBookClient::from_source(ChannelSource { receiver }, None).await?;
+        sender.send(Err(GrowthbookError::new(GrowthbookErrorCode::GrowthbookGatewayInvalidSdkKey, "revoked"))).await?;
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        assert!(!client.is_refreshing());
+        assert_eq!(1, client.consecutive_failures());
+        assert!(client.is_on("feature", None));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_serve_static_source() -> Result<(), Box<dyn std::error::Error>> {
+        let client = GrowthBookClient::from_source(StaticSource::new(ENABLED_FEATURES)?, None).await?;
+
+        assert!(client.is_on("feature", None));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_reload_file_source() -> Result<(), Box<dyn std::error::Error>> {
+        let path = std::env::temp_dir().join(format!("growthbook-source-{}.json", Uuid::now_v7()));
+        std::fs::write(&path, DISABLED_FEATURES)?;
+
+        let client = GrowthBookClient::from_source(FileSource::new(&path, Duration::from_millis(50)), None).await?;
+        assert!(!client.is_on("feature", None));
+
+        std::fs::write(&path, ENABLED_FEATURES)?;
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        std::fs::remove_file(&path)?;
+
+        assert!(client.is_on("feature", None));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_poll_http_source() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        let sdk_key = Uuid::now_v7();
+        mock_features(&mock_server, sdk_key, features_response(DISABLED_FEATURES), Some(1)).await;
+        mock_features(&mock_server, sdk_key, features_response(ENABLED_FEATURES), None).await;
+
+        let source = HttpPollingSource::new(&mock_server.uri(), sdk_key.to_string().as_str(), None, Duration::from_millis(50), Duration::from_secs(1))?;
+        let client = GrowthBookClient::from_source(source, None).await?;
+        assert!(!client.is_on("feature", None));
+
+        tokio::time::sleep(Duration::from_millis(300)).await;
+
+        assert!(client.is_on("feature", None));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_poll_http_source_on_the_exact_interval_without_jitter() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        let sdk_key = Uuid::now_v7();
+        mock_features(&mock_server, sdk_key, features_response(ENABLED_FEATURES), None).await;
+        let interval = Duration::from_millis(50);
+        let backoff_policy = BackoffPolicy {
+            jitter: 0.0,
+            ..BackoffPolicy::default()
+        };
+
+        let mut source = HttpPollingSource::new(&mock_server.uri(), sdk_key.to_string().as_str(), None, interval, Duration::from_secs(1))?.with_backoff_policy(backoff_policy);
+        source.next().await?;
+        for _ in 0..5 {
+            let started = Instant::now();
+            source.next().await?;
+            assert!(started.elapsed() >= interval);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+mod commons;
+
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, Mutex};
+
+    use growthbook_rust_sdk::model_public::FeatureResult;
+    use growthbook_rust_sdk::tracking::FeatureUsageOptions;
+    use rstest::rstest;
+    use test_context::test_context;
+
+    use crate::commons::TestContext;
+
+    type Usages = Arc<Mutex<Vec<(String, String)>>>;
+
+    fn feature_usage_callback(usages: Usages) -> impl Fn(&str, &FeatureResult) + Send + Sync {
+        move |feature_key: &str, feature_result: &FeatureResult| {
+            usages.lock().expect("Failed to lock usages").push((String::from(feature_key), feature_result.source.clone()));
+        }
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_notify_feature_usage_on_every_evaluation(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let usages = Usages::default();
+        let growthbook = ctx
+            .growthbook
+            .clone()
+            .with_feature_usage_callback(Arc::new(feature_usage_callback(usages.clone())), FeatureUsageOptions::default());
+
+        growthbook.is_on("simple-flag", None);
+        growthbook.is_off("simple-flag", None);
+        growthbook.feature_result("simple-flag", None);
+
+        assert_eq!(3, usages.lock().expect("Failed to lock usages").len());
+
+        Ok(())
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_notify_feature_usage_for_unknown_feature(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let usages = Usages::default();
+        let growthbook = ctx
+            .growthbook
+            .clone()
+            .with_feature_usage_callback(Arc::new(feature_usage_callback(usages.clone())), FeatureUsageOptions::default());
+
+        growthbook.is_on("simple-flag-with-typo", None);
+
+        assert_eq!(
+            vec![(String::from("simple-flag-with-typo"), String::from("unknownFeature"))],
+            *usages.lock().expect("Failed to lock usages")
+        );
+
+        Ok(())
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_notify_feature_usage_once_when_deduplicated(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let usages = Usages::default();
+        let options = FeatureUsageOptions {
+            deduplicate: true,
+            ..FeatureUsageOptions::default()
+        };
+        let growthbook = ctx.growthbook.clone().with_feature_usage_callback(Arc::new(feature_usage_callback(usages.clone())), options);
+
+        growthbook.is_on("simple-flag", None);
+        growthbook.is_on("simple-flag", None);
+        growthbook.is_on("simple-flag-disabled", None);
+
+        assert_eq!(2, usages.lock().expect("Failed to lock usages").len());
+
+        Ok(())
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_not_notify_feature_usage_when_sampled_out(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let usages = Usages::default();
+        let options = FeatureUsageOptions {
+            sampling_rate: 0.0,
+            ..FeatureUsageOptions::default()
+        };
+        let growthbook = ctx.growthbook.clone().with_feature_usage_callback(Arc::new(feature_usage_callback(usages.clone())), options);
+
+        growthbook.is_on("simple-flag", None);
+
+        assert!(usages.lock().expect("Failed to lock usages").is_empty());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,176 @@
+mod commons;
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    use growthbook_rust_sdk::client::GrowthBookClient;
+    use growthbook_rust_sdk::error::GrowthbookErrorCode;
+    use growthbook_rust_sdk::model_public::{FeaturesCache, RefreshStrategy};
+    use serde_json::Value;
+    use uuid::Uuid;
+    use wiremock::{MockServer, ResponseTemplate};
+
+    use crate::commons::{features_response, mock_features, mock_stream, DISABLED_FEATURES, ENABLED_FEATURES};
+
+    fn cache(max_staleness: Option<Duration>) -> FeaturesCache {
+        FeaturesCache {
+            path: std::env::temp_dir().join(format!("growthbook-{}.json", Uuid::now_v7())),
+            max_staleness,
+        }
+    }
+
+    async fn create_client(
+        mock_server: &MockServer,
+        sdk_key: Uuid,
+        cache: &FeaturesCache,
+    ) -> Result<GrowthBookClient, growthbook_rust_sdk::error::GrowthbookError> {
+        GrowthBookClient::new_with_cache(&mock_server.uri(), sdk_key.to_string().as_str(), None, cache.clone(), Some(Duration::from_millis(50)), None).await
+    }
+
+    async fn write_cache(
+        sdk_key: Uuid,
+        cache: &FeaturesCache,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        mock_features(&mock_server, sdk_key, features_response(ENABLED_FEATURES).insert_header("etag", "\"v1\""), None).await;
+        create_client(&mock_server, sdk_key, cache).await?.shutdown().await;
+        Ok(())
+    }
+
+    fn read_cache(cache: &FeaturesCache) -> Result<Value, Box<dyn std::error::Error>> {
+        Ok(serde_json::from_slice(&std::fs::read(&cache.path)?)?)
+    }
+
+    fn remove(path: &PathBuf) {
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn should_write_cache_after_successful_fetch() -> Result<(), Box<dyn std::error::Error>> {
+        let sdk_key = Uuid::now_v7();
+        let cache = cache(None);
+
+        write_cache(sdk_key, &cache).await?;
+
+        let cached: Value = serde_json::from_slice(&std::fs::read(&cache.path)?)?;
+        remove(&cache.path);
+        assert_eq!(Some("\"v1\""), cached["etag"].as_str());
+        assert!(cached["fetchedAt"].is_string());
+        assert_eq!(Some(true), cached["payload"]["features"]["feature"]["defaultValue"].as_bool());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_serve_cached_features_when_server_is_down_at_startup() -> Result<(), Box<dyn std::error::Error>> {
+        let sdk_key = Uuid::now_v7();
+        let cache = cache(Some(Duration::from_secs(60)));
+        write_cache(sdk_key, &cache).await?;
+        let mock_server = MockServer::start().await;
+        mock_features(&mock_server, sdk_key, ResponseTemplate::new(500), None).await;
+
+        let client = create_client(&mock_server, sdk_key, &cache).await?;
+        remove(&cache.path);
+
+        assert!(client.is_serving_cached_features());
+        assert!(!client.is_ready());
+        assert!(client.is_on("feature", None));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_stop_serving_cached_features_once_server_recovers() -> Result<(), Box<dyn std::error::Error>> {
+        let sdk_key = Uuid::now_v7();
+        let cache = cache(None);
+        write_cache(sdk_key, &cache).await?;
+        let mock_server = MockServer::start().await;
+        mock_features(&mock_server, sdk_key, ResponseTemplate::new(500), Some(1)).await;
+        mock_features(&mock_server, sdk_key, ResponseTemplate::new(304), None).await;
+
+        let client = create_client(&mock_server, sdk_key, &cache).await?;
+        assert!(client.is_serving_cached_features());
+
+        client.ready(Some(Duration::from_secs(2))).await?;
+        remove(&cache.path);
+
+        assert!(!client.is_serving_cached_features());
+        assert!(client.is_on("feature", None));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_ignore_stale_cache() -> Result<(), Box<dyn std::error::Error>> {
+        let sdk_key = Uuid::now_v7();
+        let cache = cache(Some(Duration::from_millis(10)));
+        write_cache(sdk_key, &cache).await?;
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let mock_server = MockServer::start().await;
+        mock_features(&mock_server, sdk_key, ResponseTemplate::new(500), None).await;
+
+        let result = create_client(&mock_server, sdk_key, &cache).await;
+        remove(&cache.path);
+
+        assert!(matches!(result.err().map(|it| it.code), Some(GrowthbookErrorCode::GrowthbookGatewayServerError)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_refresh_cache_timestamp_when_features_are_not_modified() -> Result<(), Box<dyn std::error::Error>> {
+        let sdk_key = Uuid::now_v7();
+        let cache = cache(None);
+        let mock_server = MockServer::start().await;
+        mock_features(&mock_server, sdk_key, features_response(ENABLED_FEATURES).insert_header("etag", "\"v1\""), Some(1)).await;
+        mock_features(&mock_server, sdk_key, ResponseTemplate::new(304), None).await;
+
+        let client = create_client(&mock_server, sdk_key, &cache).await?;
+        let fetched_at = read_cache(&cache)?["fetchedAt"].clone();
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        client.shutdown().await;
+
+        let cached = read_cache(&cache)?;
+        remove(&cache.path);
+        assert_ne!(fetched_at, cached["fetchedAt"]);
+        assert_eq!(Some("\"v1\""), cached["etag"].as_str());
+        assert_eq!(Some(true), cached["payload"]["features"]["feature"]["defaultValue"].as_bool());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_write_cache_for_streamed_features() -> Result<(), Box<dyn std::error::Error>> {
+        let sdk_key = Uuid::now_v7();
+        let cache = cache(None);
+        let mock_server = MockServer::start().await;
+        mock_features(&mock_server, sdk_key, features_response(ENABLED_FEATURES).insert_header("etag", "\"v1\""), None).await;
+        mock_stream(
+            &mock_server,
+            sdk_key,
+            ResponseTemplate::new(200).set_body_raw(format!("event: features\ndata: {DISABLED_FEATURES}\n\n"), "text/event-stream"),
+        )
+        .await;
+
+        let client = GrowthBookClient::builder()
+            .api_host(&mock_server.uri())
+            .client_key(&sdk_key.to_string())
+            .refresh_strategy(RefreshStrategy::Streaming)
+            .update_interval(Duration::from_secs(60))
+            .cache_path(&cache.path)
+            .build()
+            .await?;
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        client.shutdown().await;
+
+        let cached = read_cache(&cache)?;
+        remove(&cache.path);
+        assert!(client.is_off("feature", None));
+        assert!(cached["etag"].is_null());
+        assert_eq!(Some(false), cached["payload"]["features"]["feature"]["defaultValue"].as_bool());
+
+        Ok(())
+    }
+}
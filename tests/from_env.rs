@@ -0,0 +1,48 @@
+mod commons;
+
+#[cfg(test)]
+mod test {
+    use growthbook_rust_sdk::client::GrowthBookClient;
+    use growthbook_rust_sdk::error::GrowthbookErrorCode;
+    use uuid::Uuid;
+    use wiremock::MockServer;
+
+    use crate::commons::{features_response, mock_features, ENABLED_FEATURES};
+
+    // environment variables are shared by the whole process, so every scenario runs in a single test
+    #[tokio::test]
+    async fn should_create_client_from_environment() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        let sdk_key = Uuid::now_v7();
+        mock_features(&mock_server, sdk_key, features_response(ENABLED_FEATURES), None).await;
+
+        std::env::remove_var("GB_URL");
+        std::env::remove_var("GB_SDK_KEY");
+        let error = GrowthBookClient::from_env().await.err().ok_or("client should not be created")?;
+        assert!(matches!(error.code, GrowthbookErrorCode::MissingEnvironmentVariable));
+        assert!(error.message.contains("GB_URL"));
+
+        std::env::set_var("GB_URL", mock_server.uri());
+        let error = GrowthBookClient::from_env().await.err().ok_or("client should not be created")?;
+        assert!(matches!(error.code, GrowthbookErrorCode::MissingEnvironmentVariable));
+        assert!(error.message.contains("GB_SDK_KEY"));
+
+        std::env::set_var("GB_SDK_KEY", sdk_key.to_string());
+        std::env::set_var("GB_STREAMING", "maybe");
+        let error = GrowthBookClient::from_env().await.err().ok_or("client should not be created")?;
+        assert!(matches!(error.code, GrowthbookErrorCode::ParseError));
+        assert!(error.message.contains("GB_STREAMING"));
+
+        std::env::set_var("GB_STREAMING", "false");
+        std::env::set_var("GB_UPDATE_INTERVAL", "30");
+        std::env::set_var("GB_HTTP_CLIENT_TIMEOUT", "5");
+        std::env::set_var("GB_QA_MODE", "true");
+        let client = GrowthBookClient::from_env().await?;
+
+        assert!(client.is_on("feature", None));
+        assert!(client.is_refreshing());
+
+        client.shutdown().await;
+        Ok(())
+    }
+}
@@ -0,0 +1,133 @@
+mod commons;
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use growthbook_rust_sdk::client::GrowthBookClient;
+    use growthbook_rust_sdk::error::{GrowthbookError, GrowthbookErrorCode};
+    use growthbook_rust_sdk::model_public::BackoffPolicy;
+    use uuid::Uuid;
+    use wiremock::{MockServer, ResponseTemplate};
+
+    use crate::commons::{create_client, features_response, mock_features, requests, ENABLED_FEATURES};
+
+    async fn client_answered_with(response: ResponseTemplate) -> Result<GrowthBookClient, GrowthbookError> {
+        let mock_server = MockServer::start().await;
+        let sdk_key = Uuid::now_v7();
+        mock_features(&mock_server, sdk_key, response, None).await;
+
+        GrowthBookClient::new(&mock_server.uri(), sdk_key.to_string().as_str(), None, Some(Duration::from_millis(500))).await
+    }
+
+    async fn count_refreshes_after_failure(
+        response: ResponseTemplate,
+        backoff_policy: BackoffPolicy,
+    ) -> Result<(GrowthBookClient, usize), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        let sdk_key = Uuid::now_v7();
+        mock_features(&mock_server, sdk_key, features_response(ENABLED_FEATURES), Some(1)).await;
+        mock_features(&mock_server, sdk_key, response, None).await;
+
+        let client = create_client(&mock_server, sdk_key, Some(Duration::from_millis(100))).await?.with_backoff_policy(backoff_policy);
+        tokio::time::sleep(Duration::from_secs(1)).await;
+
+        assert!(client.is_on("feature", None));
+        let requests = requests(&mock_server).await;
+        Ok((client, requests))
+    }
+
+    #[tokio::test]
+    async fn should_classify_unauthorized_as_invalid_sdk_key() -> Result<(), Box<dyn std::error::Error>> {
+        let result = client_answered_with(ResponseTemplate::new(401)).await;
+
+        assert!(matches!(result.err().map(|it| it.code), Some(GrowthbookErrorCode::GrowthbookGatewayInvalidSdkKey)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_classify_not_found() -> Result<(), Box<dyn std::error::Error>> {
+        let result = client_answered_with(ResponseTemplate::new(404)).await;
+
+        assert!(matches!(result.err().map(|it| it.code), Some(GrowthbookErrorCode::GrowthbookGatewayNotFound)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_classify_rate_limit_with_retry_after() -> Result<(), Box<dyn std::error::Error>> {
+        let result = client_answered_with(ResponseTemplate::new(429).insert_header("retry-after", "7")).await;
+
+        assert!(matches!(
+            result.err().map(|it| it.code),
+            Some(GrowthbookErrorCode::GrowthbookGatewayRateLimited { retry_after: Some(it) }) if it == Duration::from_secs(7)
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_classify_bad_gateway_html_as_server_error() -> Result<(), Box<dyn std::error::Error>> {
+        let result = client_answered_with(ResponseTemplate::new(502).set_body_raw("<html>Bad Gateway</html>", "text/html")).await;
+
+        assert!(matches!(result.err().map(|it| it.code), Some(GrowthbookErrorCode::GrowthbookGatewayServerError)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_classify_slow_response_as_timeout() -> Result<(), Box<dyn std::error::Error>> {
+        let result = client_answered_with(features_response(ENABLED_FEATURES).set_delay(Duration::from_secs(2))).await;
+
+        assert!(matches!(result.err().map(|it| it.code), Some(GrowthbookErrorCode::GrowthbookGatewayTimeout)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_stop_refreshing_when_sdk_key_becomes_invalid() -> Result<(), Box<dyn std::error::Error>> {
+        let (client, requests) = count_refreshes_after_failure(ResponseTemplate::new(401), BackoffPolicy::default()).await?;
+
+        assert_eq!(2, requests);
+        assert!(!client.is_refreshing());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_not_retry_rejected_sdk_key_even_with_short_backoff() -> Result<(), Box<dyn std::error::Error>> {
+        let backoff_policy = BackoffPolicy {
+            initial_delay: Duration::from_millis(10),
+            max_delay: Duration::from_millis(50),
+            multiplier: 2.0,
+            jitter: 0.0,
+        };
+        let (client, requests) = count_refreshes_after_failure(ResponseTemplate::new(403), backoff_policy).await?;
+
+        assert_eq!(2, requests);
+        assert!(!client.is_refreshing());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_wait_retry_after_when_rate_limited() -> Result<(), Box<dyn std::error::Error>> {
+        let (client, requests) = count_refreshes_after_failure(ResponseTemplate::new(429).insert_header("retry-after", "60"), BackoffPolicy::default()).await?;
+
+        assert_eq!(2, requests);
+        assert!(client.is_refreshing());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_keep_retrying_on_timeouts() -> Result<(), Box<dyn std::error::Error>> {
+        let (client, requests) = count_refreshes_after_failure(ResponseTemplate::new(504), BackoffPolicy::default()).await?;
+
+        assert!(requests > 2);
+        assert!(client.is_refreshing());
+
+        Ok(())
+    }
+}
@@ -2,10 +2,16 @@ mod commons;
 
 #[cfg(test)]
 mod test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use growthbook_rust_sdk::client::GrowthBookClient;
     use growthbook_rust_sdk::model_public::GrowthBookAttribute;
     use rstest::rstest;
     use serde_json::json;
     use test_context::test_context;
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Level, Metadata, Subscriber};
 
     use crate::commons::TestContext;
 
@@ -40,4 +46,86 @@ mod test {
 
         Ok(())
     }
+
+    #[derive(Clone, Default)]
+    struct ErrorCounter {
+        errors: Arc<AtomicUsize>,
+    }
+
+    impl Subscriber for ErrorCounter {
+        fn enabled(
+            &self,
+            _: &Metadata<'_>,
+        ) -> bool {
+            true
+        }
+
+        fn new_span(
+            &self,
+            _: &Attributes<'_>,
+        ) -> Id {
+            Id::from_u64(1)
+        }
+
+        fn record(
+            &self,
+            _: &Id,
+            _: &Record<'_>,
+        ) {
+        }
+
+        fn record_follows_from(
+            &self,
+            _: &Id,
+            _: &Id,
+        ) {
+        }
+
+        fn event(
+            &self,
+            event: &Event<'_>,
+        ) {
+            if *event.metadata().level() == Level::ERROR {
+                self.errors.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        fn enter(
+            &self,
+            _: &Id,
+        ) {
+        }
+
+        fn exit(
+            &self,
+            _: &Id,
+        ) {
+        }
+    }
+
+    #[test]
+    fn should_report_invalid_regex_once_at_load_and_never_match() -> Result<(), Box<dyn std::error::Error>> {
+        let counter = ErrorCounter::default();
+        let payload = json!({
+            "features": {
+                "invalid-regex-rule": {
+                    "defaultValue": false,
+                    "rules": [{ "condition": { "version": { "$regex": "([0-9" } }, "force": true }]
+                }
+            }
+        });
+
+        let client = tracing::subscriber::with_default(counter.clone(), || GrowthBookClient::from_payload(payload))?;
+        assert_eq!(1, counter.errors.load(Ordering::SeqCst));
+
+        tracing::subscriber::with_default(counter.clone(), || {
+            for version in ["1.2.3", "([0-9"] {
+                let vec = GrowthBookAttribute::from(json!({ "version": version })).expect("Failed to create attributes");
+                assert!(client.is_off("invalid-regex-rule", Some(vec)));
+            }
+        });
+        assert_eq!(1, counter.errors.load(Ordering::SeqCst));
+
+        Ok(())
+    }
 }
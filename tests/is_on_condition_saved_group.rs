@@ -0,0 +1,41 @@
+mod commons;
+
+#[cfg(test)]
+mod test {
+    use growthbook_rust_sdk::model_public::GrowthBookAttribute;
+    use rstest::rstest;
+    use serde_json::json;
+    use test_context::test_context;
+
+    use crate::commons::TestContext;
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_return_enabled_true_when_user_is_in_group(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let vec = GrowthBookAttribute::from(json!({
+            "any-id": "01901d5c-fb74-743d-a532-ed582d29a7e1",
+        }))
+        .expect("Failed to create attributes");
+
+        assert!(ctx.growthbook.is_on("in-group-rule", Some(vec.clone())));
+        assert!(!ctx.growthbook.is_on("not-in-group-rule", Some(vec)));
+
+        Ok(())
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_return_enabled_false_when_user_is_not_in_group(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let vec = GrowthBookAttribute::from(json!({
+            "any-id": "018fcf64-1827-709a-a8ae-7d206aafb5e2",
+        }))
+        .expect("Failed to create attributes");
+
+        assert!(!ctx.growthbook.is_on("in-group-rule", Some(vec.clone())));
+        assert!(ctx.growthbook.is_on("not-in-group-rule", Some(vec)));
+
+        Ok(())
+    }
+}
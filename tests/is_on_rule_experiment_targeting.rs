@@ -0,0 +1,82 @@
+mod commons;
+
+#[cfg(test)]
+mod test {
+    use growthbook_rust_sdk::model_public::GrowthBookAttribute;
+    use rstest::rstest;
+    use serde_json::json;
+    use test_context::test_context;
+
+    use crate::commons::TestContext;
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_return_enabled_true_when_experiment_condition_match(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let vec = GrowthBookAttribute::from(json!({
+            "any-id": "01901d5c-fb74-743d-a532-ed582d29a7e1",
+            "country": "US",
+        }))
+        .expect("Failed to create attributes");
+
+        let result = ctx.growthbook.feature_result("experiment-rule-targeted-flag", Some(vec));
+
+        assert!(result.on);
+        assert_eq!("experiment", result.source);
+
+        Ok(())
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_return_enabled_false_when_experiment_condition_not_match(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let vec = GrowthBookAttribute::from(json!({
+            "any-id": "01901d5c-fb74-743d-a532-ed582d29a7e1",
+            "country": "BR",
+        }))
+        .expect("Failed to create attributes");
+
+        let result = ctx.growthbook.feature_result("experiment-rule-targeted-flag", Some(vec));
+
+        assert!(!result.on);
+        assert_eq!("defaultValue", result.source);
+        assert!(result.experiment_result.is_none());
+
+        Ok(())
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_return_enabled_true_when_experiment_parent_condition_is_met(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let vec = GrowthBookAttribute::from(json!({
+            "any-id": "01901d5c-fb74-743d-a532-ed582d29a7e1",
+        }))
+        .expect("Failed to create attributes");
+
+        let result = ctx.growthbook.feature_result("experiment-rule-parent-met-flag", Some(vec));
+
+        assert!(result.on);
+        assert_eq!("experiment", result.source);
+
+        Ok(())
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_return_prerequisite_when_experiment_parent_condition_is_not_met(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let vec = GrowthBookAttribute::from(json!({
+            "any-id": "01901d5c-fb74-743d-a532-ed582d29a7e1",
+        }))
+        .expect("Failed to create attributes");
+
+        let result = ctx.growthbook.feature_result("experiment-rule-parent-not-met-flag", Some(vec));
+
+        assert!(!result.on);
+        assert_eq!("prerequisite", result.source);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,124 @@
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    use growthbook_rust_sdk::client::GrowthBookClient;
+    use growthbook_rust_sdk::error::GrowthbookErrorCode;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    const DISABLED_FEATURES: &str = r#"{ "features": { "local_feature": { "defaultValue": false } } }"#;
+    const ENABLED_FEATURES: &str = r#"{ "features": { "local_feature": { "defaultValue": true }, "another_feature": { "defaultValue": true } } }"#;
+
+    fn write_file(content: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        let path = std::env::temp_dir().join(format!("growthbook-features-{}.json", Uuid::now_v7()));
+        std::fs::write(&path, content)?;
+        Ok(path)
+    }
+
+    #[test]
+    fn should_evaluate_features_from_json_payload() -> Result<(), Box<dyn std::error::Error>> {
+        let client = GrowthBookClient::from_payload(ENABLED_FEATURES)?;
+
+        assert!(client.is_on("local_feature", None));
+        assert!(client.is_ready());
+        assert!(!client.is_refreshing());
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_expose_loaded_features_and_experiments() -> Result<(), Box<dyn std::error::Error>> {
+        let client = GrowthBookClient::from_payload(ENABLED_FEATURES)?;
+
+        assert_eq!(2, client.features().len());
+        assert_eq!(Some(json!(true)), client.feature("local_feature").and_then(|it| it.default_value));
+        assert!(client.feature("missing_feature").is_none());
+        assert!(client.experiments().is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_expose_current_snapshot() -> Result<(), Box<dyn std::error::Error>> {
+        let client = GrowthBookClient::from_payload(ENABLED_FEATURES)?;
+
+        let snapshot = client.snapshot();
+
+        assert_eq!(2, snapshot.features.len());
+        assert!(snapshot.check("local_feature", &None, None).on);
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_evaluate_features_from_value_payload() -> Result<(), Box<dyn std::error::Error>> {
+        let client = GrowthBookClient::from_payload(json!({ "features": { "local_feature": { "defaultValue": true } } }))?;
+
+        assert!(client.is_on("local_feature", None));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_fail_with_invalid_payload() -> Result<(), Box<dyn std::error::Error>> {
+        let result = GrowthBookClient::from_payload("{ features");
+
+        assert!(matches!(result.err().map(|it| it.code), Some(GrowthbookErrorCode::ParseError)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_evaluate_features_from_file() -> Result<(), Box<dyn std::error::Error>> {
+        let path = write_file(ENABLED_FEATURES)?;
+
+        let client = GrowthBookClient::from_file(&path)?;
+        std::fs::remove_file(&path)?;
+
+        assert!(client.is_on("local_feature", None));
+        assert_eq!(2, client.total_features());
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_fail_when_file_is_missing() -> Result<(), Box<dyn std::error::Error>> {
+        let result = GrowthBookClient::from_file(std::env::temp_dir().join(format!("missing-{}.json", Uuid::now_v7())));
+
+        assert!(matches!(result.err().map(|it| it.code), Some(GrowthbookErrorCode::IoError)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_reload_watched_file_when_it_changes() -> Result<(), Box<dyn std::error::Error>> {
+        let path = write_file(DISABLED_FEATURES)?;
+
+        let client = GrowthBookClient::from_file_watched(&path, Some(Duration::from_millis(50)))?;
+        assert!(!client.is_on("local_feature", None));
+
+        std::fs::write(&path, ENABLED_FEATURES)?;
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        std::fs::remove_file(&path)?;
+
+        assert!(client.is_on("local_feature", None));
+        assert!(client.is_refreshing());
+        client.shutdown().await;
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_fail_to_watch_file_outside_tokio_runtime() -> Result<(), Box<dyn std::error::Error>> {
+        let path = write_file(DISABLED_FEATURES)?;
+
+        let result = GrowthBookClient::from_file_watched(&path, None);
+        std::fs::remove_file(&path)?;
+
+        assert!(matches!(result.err().map(|it| it.code), Some(GrowthbookErrorCode::MissingTokioRuntime)));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+mod commons;
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use growthbook_rust_sdk::client::GrowthBookClient;
+    use growthbook_rust_sdk::error::GrowthbookErrorCode;
+    use growthbook_rust_sdk::model_public::{FeaturesPayload, NonBlockingOptions, RefreshStrategy};
+    use uuid::Uuid;
+    use wiremock::{MockServer, ResponseTemplate};
+
+    use crate::commons::{features_response, mock_features, ENABLED_FEATURES};
+
+    async fn mock_recovering_features(
+        mock_server: &MockServer,
+        sdk_key: Uuid,
+        failure: ResponseTemplate,
+        failures: Option<u64>,
+    ) {
+        mock_features(mock_server, sdk_key, failure, failures).await;
+        mock_features(mock_server, sdk_key, features_response(ENABLED_FEATURES), None).await;
+    }
+
+    fn create_client(
+        mock_server: &MockServer,
+        sdk_key: Uuid,
+        bootstrap: Option<&str>,
+        refresh_strategy: RefreshStrategy,
+    ) -> Result<GrowthBookClient, Box<dyn std::error::Error>> {
+        let options = NonBlockingOptions {
+            bootstrap: bootstrap.map(FeaturesPayload::from),
+            refresh_strategy,
+            update_interval: Some(Duration::from_millis(50)),
+            ..NonBlockingOptions::default()
+        };
+        let client = GrowthBookClient::new_non_blocking(&mock_server.uri(), sdk_key.to_string().as_str(), options)?;
+        Ok(client)
+    }
+
+    #[tokio::test]
+    async fn should_start_empty_and_become_ready_once_server_recovers() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        let sdk_key = Uuid::now_v7();
+        mock_recovering_features(&mock_server, sdk_key, ResponseTemplate::new(503), Some(3)).await;
+
+        let client = create_client(&mock_server, sdk_key, None, RefreshStrategy::Polling)?;
+
+        assert!(!client.is_ready());
+        assert!(!client.is_on("feature", None));
+
+        client.ready(Some(Duration::from_secs(3))).await?;
+
+        assert!(client.is_ready());
+        assert!(client.is_on("feature", None));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_serve_bootstrap_payload_until_first_fetch() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        let sdk_key = Uuid::now_v7();
+        mock_recovering_features(&mock_server, sdk_key, ResponseTemplate::new(500), None).await;
+
+        let client = create_client(&mock_server, sdk_key, Some(ENABLED_FEATURES), RefreshStrategy::Polling)?;
+        let result = client.ready(Some(Duration::from_millis(200))).await;
+
+        assert!(matches!(result.err().map(|it| it.code), Some(GrowthbookErrorCode::GrowthbookClientNotReady)));
+        assert!(!client.is_ready());
+        assert!(client.is_on("feature", None));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_fail_ready_when_sdk_key_is_invalid() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        let sdk_key = Uuid::now_v7();
+        mock_recovering_features(&mock_server, sdk_key, ResponseTemplate::new(401), None).await;
+
+        let client = create_client(&mock_server, sdk_key, None, RefreshStrategy::Streaming)?;
+        let result = tokio::time::timeout(Duration::from_secs(3), client.ready(None)).await?;
+
+        assert!(matches!(result.err().map(|it| it.code), Some(GrowthbookErrorCode::GrowthbookClientNotReady)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_fail_outside_tokio_runtime() -> Result<(), Box<dyn std::error::Error>> {
+        let result = GrowthBookClient::new_non_blocking("http://localhost", "sdk-key", NonBlockingOptions::default());
+
+        assert!(matches!(result.err().map(|it| it.code), Some(GrowthbookErrorCode::MissingTokioRuntime)));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,70 @@
+mod commons;
+
+#[cfg(test)]
+mod test {
+    use growthbook_rust_sdk::model_public::{Experiment, GrowthBookAttribute};
+    use rstest::rstest;
+    use serde_json::json;
+    use test_context::test_context;
+
+    use crate::commons::TestContext;
+
+    const URL: &str = "https://www.example.com/checkout?any-experiment-key=2";
+
+    fn attributes() -> Option<Vec<GrowthBookAttribute>> {
+        Some(
+            GrowthBookAttribute::from(json!({
+                "any-id": "01901d5c-fb74-743d-a532-ed582d29a7e1",
+            }))
+            .expect("Failed to create attributes"),
+        )
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_force_variation_from_query_string_when_overrides_are_enabled(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let growthbook = ctx.growthbook.clone().with_query_string_overrides(true);
+
+        let result = growthbook.feature_result_for_url("experiment-rule-condition-flag", attributes(), URL);
+
+        let experiment_result = result.experiment_result.expect("Failed to get experiment_result");
+        assert_eq!(2, experiment_result.variation_id);
+        assert!(experiment_result.in_experiment);
+        assert!(!experiment_result.hash_used);
+
+        Ok(())
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_ignore_query_string_when_overrides_are_disabled(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let result = ctx.growthbook.feature_result_for_url("experiment-rule-condition-flag", attributes(), URL);
+
+        let experiment_result = result.experiment_result.expect("Failed to get experiment_result");
+        assert!(experiment_result.hash_used);
+
+        Ok(())
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_force_inline_experiment_from_query_string(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let growthbook = ctx.growthbook.clone().with_query_string_overrides(true);
+        let experiment = Experiment {
+            key: String::from("inline-experiment"),
+            variations: vec![json!("control"), json!("treatment")],
+            active: Some(false),
+            ..Experiment::default()
+        };
+
+        let result = growthbook.run_for_url(&experiment, None, "https://www.example.com/?inline-experiment=1");
+
+        assert_eq!(json!("treatment"), result.value);
+        assert!(result.in_experiment);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,68 @@
+mod commons;
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use growthbook_rust_sdk::model_public::BackoffPolicy;
+    use uuid::Uuid;
+    use wiremock::{MockServer, ResponseTemplate};
+
+    use crate::commons::{create_client, features_response, mock_features, requests, ENABLED_FEATURES};
+
+    async fn mock_failing_features(
+        mock_server: &MockServer,
+        sdk_key: Uuid,
+        failures: u64,
+    ) {
+        mock_features(mock_server, sdk_key, features_response(ENABLED_FEATURES), Some(1)).await;
+        mock_features(mock_server, sdk_key, ResponseTemplate::new(500), Some(failures)).await;
+        mock_features(mock_server, sdk_key, features_response(ENABLED_FEATURES), None).await;
+    }
+
+    fn backoff_policy() -> BackoffPolicy {
+        BackoffPolicy {
+            initial_delay: Duration::from_millis(50),
+            max_delay: Duration::from_secs(10),
+            multiplier: 2.0,
+            jitter: 0.0,
+        }
+    }
+
+    #[tokio::test]
+    async fn should_back_off_exponentially_after_consecutive_failures() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        let sdk_key = Uuid::now_v7();
+        mock_failing_features(&mock_server, sdk_key, 100).await;
+
+        let client = create_client(&mock_server, sdk_key, Some(Duration::from_millis(50))).await?.with_backoff_policy(backoff_policy());
+        tokio::time::sleep(Duration::from_millis(1200)).await;
+
+        let requests = requests(&mock_server).await;
+        assert!((3..=8).contains(&requests), "unexpected number of requests: {requests}");
+        assert!(client.consecutive_failures() >= 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_reset_failures_after_successful_refresh() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        let sdk_key = Uuid::now_v7();
+        mock_failing_features(&mock_server, sdk_key, 2).await;
+
+        let client = create_client(&mock_server, sdk_key, Some(Duration::from_millis(50))).await?.with_backoff_policy(backoff_policy());
+        let created_at = client.last_successful_refresh();
+        assert!(created_at.is_some());
+        assert_eq!(0, client.consecutive_failures());
+
+        tokio::time::sleep(Duration::from_millis(120)).await;
+        assert!(client.consecutive_failures() > 0);
+
+        tokio::time::sleep(Duration::from_millis(600)).await;
+        assert_eq!(0, client.consecutive_failures());
+        assert!(client.last_successful_refresh() > created_at);
+
+        Ok(())
+    }
+}
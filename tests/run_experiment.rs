@@ -0,0 +1,187 @@
+mod commons;
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use growthbook_rust_sdk::model_public::{Experiment, GrowthBookAttribute, UrlPattern};
+    use growthbook_rust_sdk::sticky_bucket::service::InMemoryStickyBucketService;
+    use rstest::rstest;
+    use serde_json::json;
+    use test_context::test_context;
+
+    use crate::commons::TestContext;
+
+    fn experiment() -> Experiment {
+        Experiment {
+            key: String::from("inline-experiment"),
+            variations: vec![json!("control"), json!("treatment")],
+            hash_attribute: Some(String::from("any-id")),
+            ..Experiment::default()
+        }
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_assign_variation_when_user_is_in_experiment(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let vec = GrowthBookAttribute::from(json!({
+            "any-id": "018fcf11-bb67-7789-8d10-fcbb7de4ff7b",
+        }))
+        .expect("Failed to create attributes");
+
+        let result = ctx.growthbook.run(&experiment(), Some(vec));
+
+        assert!(result.in_experiment);
+        assert!(result.hash_used);
+        assert_eq!("", result.feature_id);
+        assert_eq!(Some(String::from("any-id")), result.hash_attribute);
+        assert_eq!(experiment().variations[result.variation_id as usize], result.value);
+
+        Ok(())
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_not_be_in_experiment_when_hash_attribute_is_missing(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let vec = GrowthBookAttribute::from(json!({
+            "any-other-id": "018fcf11-bb67-7789-8d10-fcbb7de4ff7b",
+        }))
+        .expect("Failed to create attributes");
+
+        let result = ctx.growthbook.run(&experiment(), Some(vec));
+
+        assert!(!result.in_experiment);
+        assert!(!result.hash_used);
+        assert_eq!(json!("control"), result.value);
+
+        Ok(())
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_not_be_in_experiment_when_condition_does_not_match(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let vec = GrowthBookAttribute::from(json!({
+            "any-id": "018fcf11-bb67-7789-8d10-fcbb7de4ff7b",
+            "country": "BR",
+        }))
+        .expect("Failed to create attributes");
+        let experiment = Experiment {
+            condition: Some(json!({ "country": "US" }).into()),
+            ..experiment()
+        };
+
+        let result = ctx.growthbook.run(&experiment, Some(vec));
+
+        assert!(!result.in_experiment);
+        assert_eq!(0, result.variation_id);
+
+        Ok(())
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_not_be_in_experiment_when_experiment_is_inactive(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let vec = GrowthBookAttribute::from(json!({
+            "any-id": "018fcf11-bb67-7789-8d10-fcbb7de4ff7b",
+        }))
+        .expect("Failed to create attributes");
+        let experiment = Experiment { active: Some(false), ..experiment() };
+
+        let result = ctx.growthbook.run(&experiment, Some(vec));
+
+        assert!(!result.in_experiment);
+
+        Ok(())
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_ignore_fallback_attribute_without_sticky_bucket_service(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let vec = GrowthBookAttribute::from(json!({
+            "device-id": "018fcf11-bb67-7789-8d10-fcbb7de4ff7b",
+        }))
+        .expect("Failed to create attributes");
+        let experiment = Experiment {
+            fallback_attribute: Some(String::from("device-id")),
+            ..experiment()
+        };
+
+        let result = ctx.growthbook.run(&experiment, Some(vec));
+
+        assert!(!result.in_experiment);
+        assert!(!result.hash_used);
+
+        Ok(())
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_use_fallback_attribute_with_sticky_bucket_service(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let growthbook = ctx.growthbook.clone().with_sticky_bucket_service(Arc::new(InMemoryStickyBucketService::default()));
+        let vec = GrowthBookAttribute::from(json!({
+            "device-id": "018fcf11-bb67-7789-8d10-fcbb7de4ff7b",
+        }))
+        .expect("Failed to create attributes");
+        let experiment = Experiment {
+            fallback_attribute: Some(String::from("device-id")),
+            ..experiment()
+        };
+
+        let result = growthbook.run(&experiment, Some(vec));
+
+        assert!(result.in_experiment);
+        assert_eq!(Some(String::from("device-id")), result.hash_attribute);
+
+        Ok(())
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_only_be_in_experiment_when_url_is_targeted(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let vec = GrowthBookAttribute::from(json!({
+            "any-id": "018fcf11-bb67-7789-8d10-fcbb7de4ff7b",
+        }))
+        .expect("Failed to create attributes");
+        let experiment = Experiment {
+            url_patterns: Some(vec![UrlPattern::new("simple", "https://www.example.com/checkout", None)]),
+            ..experiment()
+        };
+
+        let targeted = ctx.growthbook.run_for_url(&experiment, Some(vec.clone()), "https://www.example.com/checkout");
+        let not_targeted = ctx.growthbook.run_for_url(&experiment, Some(vec), "https://www.example.com/home");
+
+        assert!(targeted.in_experiment);
+        assert!(!not_targeted.in_experiment);
+        assert_eq!(0, not_targeted.variation_id);
+
+        Ok(())
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_not_be_in_url_targeted_experiment_without_url(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let vec = GrowthBookAttribute::from(json!({
+            "any-id": "018fcf11-bb67-7789-8d10-fcbb7de4ff7b",
+        }))
+        .expect("Failed to create attributes");
+        let experiment = Experiment {
+            url_patterns: Some(vec![UrlPattern::new("simple", "https://www.example.com/checkout", None)]),
+            ..experiment()
+        };
+
+        let result = ctx.growthbook.run(&experiment, Some(vec));
+
+        assert!(!result.in_experiment);
+        assert_eq!(0, result.variation_id);
+
+        Ok(())
+    }
+}
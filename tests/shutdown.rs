@@ -0,0 +1,79 @@
+mod commons;
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use growthbook_rust_sdk::client::GrowthBookClient;
+    use growthbook_rust_sdk::error::GrowthbookErrorCode;
+    use uuid::Uuid;
+    use wiremock::{MockServer, ResponseTemplate};
+
+    use crate::commons::{create_client, features_response, mock_features, requests, ENABLED_FEATURES};
+
+    async fn refreshing_client(mock_server: &MockServer) -> Result<GrowthBookClient, Box<dyn std::error::Error>> {
+        let sdk_key = Uuid::now_v7();
+        mock_features(mock_server, sdk_key, features_response(ENABLED_FEATURES), None).await;
+
+        let client = create_client(mock_server, sdk_key, Some(Duration::from_millis(50))).await?;
+        Ok(client)
+    }
+
+    #[tokio::test]
+    async fn should_stop_refreshing_after_shutdown() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        let client = refreshing_client(&mock_server).await?;
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(client.is_refreshing());
+
+        client.shutdown().await;
+        let requests_after_shutdown = requests(&mock_server).await;
+        tokio::time::sleep(Duration::from_millis(300)).await;
+
+        assert!(!client.is_refreshing());
+        assert_eq!(requests_after_shutdown, requests(&mock_server).await);
+        assert!(client.is_on("feature", None));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_stop_refreshing_when_last_clone_is_dropped() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        let client = refreshing_client(&mock_server).await?;
+        let clone = client.clone();
+
+        drop(client);
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(clone.is_refreshing());
+
+        drop(clone);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let requests_after_drop = requests(&mock_server).await;
+        tokio::time::sleep(Duration::from_millis(300)).await;
+
+        assert_eq!(requests_after_drop, requests(&mock_server).await);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_fail_ready_when_shutdown_before_first_fetch() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        let sdk_key = Uuid::now_v7();
+        mock_features(&mock_server, sdk_key, ResponseTemplate::new(503), None).await;
+        let client = GrowthBookClient::builder()
+            .api_host(&mock_server.uri())
+            .client_key(&sdk_key.to_string())
+            .non_blocking(true)
+            .build()
+            .await?;
+
+        client.shutdown().await;
+        let result = tokio::time::timeout(Duration::from_secs(1), client.ready(None)).await?;
+
+        assert!(matches!(result.err().map(|it| it.code), Some(GrowthbookErrorCode::GrowthbookClientNotReady)));
+
+        Ok(())
+    }
+}
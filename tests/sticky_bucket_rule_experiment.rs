@@ -0,0 +1,66 @@
+mod commons;
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use growthbook_rust_sdk::model_public::GrowthBookAttribute;
+    use growthbook_rust_sdk::sticky_bucket::model::StickyAssignmentsDocument;
+    use growthbook_rust_sdk::sticky_bucket::service::{InMemoryStickyBucketService, StickyBucketService};
+    use rstest::rstest;
+    use serde_json::json;
+    use test_context::test_context;
+
+    use crate::commons::TestContext;
+
+    const USER_ID: &str = "01901d5c-fb74-743d-a532-ed582d29a7e1";
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_save_sticky_bucket_assignment_when_user_is_bucketed(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let service = Arc::new(InMemoryStickyBucketService::default());
+        let growthbook = ctx.growthbook.clone().with_sticky_bucket_service(service.clone());
+        let vec = GrowthBookAttribute::from(json!({
+            "any-id": USER_ID,
+        }))
+        .expect("Failed to create attributes");
+
+        let result = growthbook.feature_result("experiment-rule-condition-flag", Some(vec));
+
+        let experiment_result = result.experiment_result.expect("Failed to get experiment_result");
+        assert_eq!("0", experiment_result.key);
+        assert!(!experiment_result.sticky_bucket_used);
+        let document = service.get_assignments("any-id", USER_ID).expect("Failed to get sticky bucket document");
+        assert_eq!(Some(&String::from("0")), document.assignments.get("any-experiment-key__0"));
+
+        Ok(())
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_keep_sticky_bucket_variation_when_hash_points_to_another_one(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let service = Arc::new(InMemoryStickyBucketService::default());
+        service.save_assignments(StickyAssignmentsDocument::new(
+            "any-id",
+            USER_ID,
+            HashMap::from([(String::from("any-experiment-key__0"), String::from("1"))]),
+        ));
+        let growthbook = ctx.growthbook.clone().with_sticky_bucket_service(service);
+        let vec = GrowthBookAttribute::from(json!({
+            "any-id": USER_ID,
+        }))
+        .expect("Failed to create attributes");
+
+        let result = growthbook.feature_result("experiment-rule-condition-flag", Some(vec));
+
+        assert!(result.on);
+        let experiment_result = result.experiment_result.expect("Failed to get experiment_result");
+        assert_eq!("1", experiment_result.key);
+        assert!(experiment_result.sticky_bucket_used);
+
+        Ok(())
+    }
+}
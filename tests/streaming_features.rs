@@ -0,0 +1,191 @@
+mod commons;
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use growthbook_rust_sdk::client::GrowthBookClient;
+    use growthbook_rust_sdk::model_public::{BackoffPolicy, RefreshStrategy};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use uuid::Uuid;
+    use wiremock::{MockServer, ResponseTemplate};
+
+    use crate::commons::{features_response, mock_features, mock_stream, DISABLED_FEATURES, ENABLED_FEATURES};
+
+    async fn mock_updated_features(
+        mock_server: &MockServer,
+        sdk_key: Uuid,
+    ) {
+        mock_features(mock_server, sdk_key, features_response(DISABLED_FEATURES), Some(1)).await;
+        mock_features(mock_server, sdk_key, features_response(ENABLED_FEATURES), None).await;
+    }
+
+    async fn streaming_client(
+        mock_server: &MockServer,
+        sdk_key: Uuid,
+        update_interval: Duration,
+    ) -> Result<GrowthBookClient, Box<dyn std::error::Error>> {
+        let client = GrowthBookClient::new_with_refresh_strategy(&mock_server.uri(), sdk_key.to_string().as_str(), None, RefreshStrategy::Streaming, Some(update_interval), None).await?;
+        Ok(client)
+    }
+
+    // wiremock always sends the whole body, so a stream that opens and then goes silent needs a raw server
+    async fn silent_stream_server() -> Result<String, Box<dyn std::error::Error>> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let address = listener.local_addr()?;
+        let features_requests = Arc::new(AtomicUsize::new(0));
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let features_requests = features_requests.clone();
+                tokio::spawn(async move {
+                    let mut request = [0u8; 4096];
+                    let Ok(size) = socket.read(&mut request).await else {
+                        return;
+                    };
+                    if String::from_utf8_lossy(&request[..size]).starts_with("GET /sub/") {
+                        let _ = socket.write_all(b"HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\ntransfer-encoding: chunked\r\n\r\n").await;
+                        std::future::pending::<()>().await;
+                    }
+                    let body = if features_requests.fetch_add(1, Ordering::SeqCst) == 0 {
+                        DISABLED_FEATURES
+                    } else {
+                        ENABLED_FEATURES
+                    };
+                    let response = format!("HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}", body.len());
+                    let _ = socket.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+        Ok(format!("http://{address}"))
+    }
+
+    async fn wait_until_on(
+        client: &GrowthBookClient,
+        feature_name: &str,
+    ) -> bool {
+        for _ in 0..50 {
+            if client.is_on(feature_name, None) {
+                return true;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        false
+    }
+
+    #[tokio::test]
+    async fn should_apply_features_event_from_stream() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        let sdk_key = Uuid::now_v7();
+        mock_features(&mock_server, sdk_key, features_response(DISABLED_FEATURES), None).await;
+        mock_stream(
+            &mock_server,
+            sdk_key,
+            ResponseTemplate::new(200).set_body_raw(format!(": connected\n\nevent: features\ndata: {ENABLED_FEATURES}\n\n"), "text/event-stream"),
+        )
+        .await;
+
+        let client = streaming_client(&mock_server, sdk_key, Duration::from_secs(60)).await?;
+
+        assert!(wait_until_on(&client, "feature").await);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_fetch_features_when_stream_notifies_update() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        let sdk_key = Uuid::now_v7();
+        mock_updated_features(&mock_server, sdk_key).await;
+        mock_stream(
+            &mock_server,
+            sdk_key,
+            ResponseTemplate::new(200).set_body_raw("event: features-updated\ndata: {}\n\n", "text/event-stream"),
+        )
+        .await;
+
+        let client = streaming_client(&mock_server, sdk_key, Duration::from_secs(60)).await?;
+
+        assert!(!client.is_on("feature", None));
+        assert!(wait_until_on(&client, "feature").await);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_fall_back_to_polling_when_stream_is_unavailable() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        let sdk_key = Uuid::now_v7();
+        mock_updated_features(&mock_server, sdk_key).await;
+        mock_stream(&mock_server, sdk_key, ResponseTemplate::new(404)).await;
+
+        let client = streaming_client(&mock_server, sdk_key, Duration::from_secs(1)).await?;
+
+        assert!(!client.is_on("feature", None));
+        assert!(wait_until_on(&client, "feature").await);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_poll_when_stream_stays_silent() -> Result<(), Box<dyn std::error::Error>> {
+        let api_url = silent_stream_server().await?;
+
+        let client = GrowthBookClient::new_with_refresh_strategy(&api_url, "sdk-key", None, RefreshStrategy::Streaming, Some(Duration::from_secs(1)), None).await?;
+
+        assert!(!client.is_on("feature", None));
+        assert!(wait_until_on(&client, "feature").await);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_skip_malformed_features_event() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        let sdk_key = Uuid::now_v7();
+        mock_features(&mock_server, sdk_key, features_response(DISABLED_FEATURES), None).await;
+        mock_stream(
+            &mock_server,
+            sdk_key,
+            ResponseTemplate::new(200).set_body_raw(format!("event: features\ndata: {{ features\n\nevent: features\ndata: {ENABLED_FEATURES}\n\n"), "text/event-stream"),
+        )
+        .await;
+
+        let client = streaming_client(&mock_server, sdk_key, Duration::from_secs(60)).await?;
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        assert!(client.is_on("feature", None));
+        assert_eq!(0, client.consecutive_failures());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_reconnect_stream_with_client_backoff_policy() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        let sdk_key = Uuid::now_v7();
+        mock_updated_features(&mock_server, sdk_key).await;
+        mock_stream(&mock_server, sdk_key, ResponseTemplate::new(200).set_body_raw("", "text/event-stream")).await;
+
+        let _client = streaming_client(&mock_server, sdk_key, Duration::from_secs(60)).await?.with_backoff_policy(BackoffPolicy {
+            initial_delay: Duration::from_millis(50),
+            max_delay: Duration::from_millis(50),
+            multiplier: 1.0,
+            jitter: 0.0,
+        });
+        tokio::time::sleep(Duration::from_millis(1000)).await;
+
+        let subscriptions = mock_server
+            .received_requests()
+            .await
+            .unwrap_or_default()
+            .iter()
+            .filter(|request| request.url.path().starts_with("/sub/"))
+            .count();
+        assert!(subscriptions >= 5, "unexpected number of subscriptions: {subscriptions}");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,152 @@
+mod commons;
+
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, Mutex};
+
+    use growthbook_rust_sdk::model_public::{Experiment, ExperimentResult, GrowthBookAttribute};
+    use rstest::rstest;
+    use serde_json::json;
+    use test_context::test_context;
+
+    use crate::commons::TestContext;
+
+    type Tracked = Arc<Mutex<Vec<(String, i64)>>>;
+
+    fn tracking_callback(tracked: Tracked) -> impl Fn(&Experiment, &ExperimentResult) + Send + Sync {
+        move |experiment: &Experiment, experiment_result: &ExperimentResult| {
+            tracked.lock().expect("Failed to lock tracked").push((experiment.key.clone(), experiment_result.variation_id));
+        }
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_track_experiment_exposure_only_once_per_user(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let tracked = Tracked::default();
+        let growthbook = ctx.growthbook.clone().with_tracking_callback(Arc::new(tracking_callback(tracked.clone())));
+        let vec = GrowthBookAttribute::from(json!({
+            "any-id": "01901d5c-fb74-743d-a532-ed582d29a7e1",
+        }))
+        .expect("Failed to create attributes");
+
+        let first = growthbook.feature_result("experiment-rule-condition-flag", Some(vec.clone()));
+        let second = growthbook.feature_result("experiment-rule-condition-flag", Some(vec));
+
+        let experiment_result = first.experiment_result.expect("Failed to get experiment_result");
+        assert_eq!(experiment_result.variation_id, second.experiment_result.expect("Failed to get experiment_result").variation_id);
+        assert_eq!(
+            vec![(String::from("any-experiment-key"), experiment_result.variation_id)],
+            *tracked.lock().expect("Failed to lock tracked")
+        );
+
+        Ok(())
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_track_experiment_exposure_for_each_user(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let tracked = Tracked::default();
+        let growthbook = ctx.growthbook.clone().with_tracking_callback(Arc::new(tracking_callback(tracked.clone())));
+        let experiment = Experiment {
+            key: String::from("inline-experiment"),
+            variations: vec![json!("control"), json!("treatment")],
+            ..Experiment::default()
+        };
+
+        for id in ["018fcf11-bb67-7789-8d10-fcbb7de4ff7b", "01901d5c-fb74-743d-a532-ed582d29a7e1"] {
+            let vec = GrowthBookAttribute::from(json!({ "id": id })).expect("Failed to create attributes");
+            growthbook.run(&experiment, Some(vec.clone()));
+            growthbook.run(&experiment, Some(vec));
+        }
+
+        assert_eq!(2, tracked.lock().expect("Failed to lock tracked").len());
+
+        Ok(())
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_track_experiment_evaluated_for_prerequisite_feature(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let tracked = Tracked::default();
+        let growthbook = ctx.growthbook.clone().with_tracking_callback(Arc::new(tracking_callback(tracked.clone())));
+        let vec = GrowthBookAttribute::from(json!({
+            "any-id": "01901d5c-fb74-743d-a532-ed582d29a7e1",
+        }))
+        .expect("Failed to create attributes");
+
+        let result = growthbook.feature_result("experiment-prerequisite-flag", Some(vec.clone()));
+        let tracked_by_prerequisite = tracked.lock().expect("Failed to lock tracked").clone();
+        let parent_result = growthbook.feature_result("experiment-rule-condition-flag", Some(vec));
+
+        assert!(result.on);
+        assert!(result.experiment_result.is_none());
+        let parent_experiment_result = parent_result.experiment_result.expect("Failed to get experiment_result");
+        assert_eq!(vec![(String::from("any-experiment-key"), parent_experiment_result.variation_id)], tracked_by_prerequisite);
+
+        Ok(())
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_not_track_when_user_is_not_in_experiment(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let tracked = Tracked::default();
+        let growthbook = ctx.growthbook.clone().with_tracking_callback(Arc::new(tracking_callback(tracked.clone())));
+        let vec = GrowthBookAttribute::from(json!({
+            "any-id": "01901d5c-fb74-743d-a532-ed582d29a7e1",
+        }))
+        .expect("Failed to create attributes");
+
+        growthbook.feature_result("experiment-rule-condition-zero-coverage-flag", Some(vec));
+
+        assert!(tracked.lock().expect("Failed to lock tracked").is_empty());
+
+        Ok(())
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_track_exposures_whose_concatenated_keys_collide(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let tracked = Tracked::default();
+        let growthbook = ctx.growthbook.clone().with_tracking_callback(Arc::new(tracking_callback(tracked.clone())));
+        let experiment = |key: &str| Experiment {
+            key: String::from(key),
+            variations: vec![json!("control"), json!("treatment")],
+            weights: Some(vec![1.0, 0.0]),
+            ..Experiment::default()
+        };
+
+        growthbook.run(&experiment("2exp"), Some(GrowthBookAttribute::from(json!({ "id": "1" }))?));
+        growthbook.run(&experiment("exp"), Some(GrowthBookAttribute::from(json!({ "id": "12" }))?));
+
+        assert_eq!(2, tracked.lock().expect("Failed to lock tracked").len());
+
+        Ok(())
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_track_again_once_exposure_is_evicted(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let tracked = Tracked::default();
+        let growthbook = ctx.growthbook.clone().with_tracking_callback(Arc::new(tracking_callback(tracked.clone())));
+        let experiment = Experiment {
+            key: String::from("inline-experiment"),
+            variations: vec![json!("control"), json!("treatment")],
+            ..Experiment::default()
+        };
+
+        for id in 0..=10_000 {
+            growthbook.run(&experiment, Some(GrowthBookAttribute::from(json!({ "id": id.to_string() }))?));
+        }
+        growthbook.run(&experiment, Some(GrowthBookAttribute::from(json!({ "id": "0" }))?));
+
+        assert_eq!(10_002, tracked.lock().expect("Failed to lock tracked").len());
+
+        Ok(())
+    }
+}
@@ -67,6 +67,8 @@ mod test {
 
         let result = client.is_on("another_feature", None);
         assert!(result);
+
+        client.shutdown().await;
         Ok(())
     }
 }
@@ -0,0 +1,67 @@
+mod commons;
+
+#[cfg(test)]
+mod test {
+    use growthbook_rust_sdk::model_public::GrowthBookAttribute;
+    use rstest::rstest;
+    use serde_json::json;
+    use test_context::test_context;
+
+    use crate::commons::TestContext;
+
+    fn attributes() -> Option<Vec<GrowthBookAttribute>> {
+        Some(
+            GrowthBookAttribute::from(json!({
+                "any-id": "018fcf11-bb67-7789-8d10-fcbb7de4ff7b",
+            }))
+            .expect("Failed to create attributes"),
+        )
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_redirect_when_url_is_targeted(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let redirect = ctx.growthbook.redirect("https://www.example.com/checkout?coupon=abc", attributes()).expect("Failed to get redirect");
+
+        assert_eq!("https://www.example.com/checkout-new?coupon=abc", redirect.url);
+        assert_eq!("checkout-redirect", redirect.experiment.key);
+        assert!(redirect.experiment_result.in_experiment);
+        assert_eq!(1, redirect.experiment_result.variation_id);
+
+        Ok(())
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_not_redirect_when_url_is_not_targeted(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let redirect = ctx.growthbook.redirect("https://www.example.com/home", attributes());
+
+        assert!(redirect.is_none());
+
+        Ok(())
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_not_redirect_when_url_is_excluded(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let redirect = ctx.growthbook.redirect("https://www.example.com/checkout?internal=true", attributes());
+
+        assert!(redirect.is_none());
+
+        Ok(())
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_not_redirect_when_hash_attribute_is_missing(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let redirect = ctx.growthbook.redirect("https://www.example.com/checkout", None);
+
+        assert!(redirect.is_none());
+
+        Ok(())
+    }
+}